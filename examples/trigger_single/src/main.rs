@@ -1,14 +1,16 @@
 extern crate novu;
 use novu::{
     events::{
-        AllowedPayloadValues, TriggerPayload, TriggerRecipientBuilder, TriggerRecipientsType,
+        AllowedPayloadValues, EmailOverrides, TriggerOverrides, TriggerPayload,
+        TriggerRecipientBuilder, TriggerRecipientsType,
     },
     Novu,
 };
-use std::{collections::HashMap, env};
+use std::collections::HashMap;
 #[async_std::main]
 async fn main() {
-    let novu = Novu::new(env::var("API_TOKEN").unwrap(), None).unwrap();
+    // Reads NOVU_API_KEY, and optionally NOVU_API_URL, NOVU_REGION, etc.
+    let novu = Novu::from_env().unwrap();
 
     let mut payload: HashMap<String, AllowedPayloadValues> = HashMap::new();
     payload.insert(
@@ -17,16 +19,26 @@ async fn main() {
     );
 
     let result = novu
-        .trigger(TriggerPayload {
-            name: "testing".to_string(),
-            payload,
-            to: TriggerRecipientsType::Single(
-                TriggerRecipientBuilder::new("testing")
-                    .first_name("Test")
-                    .email("me+test@midka.dev")
-                    .build(),
-            ),
-        })
+        .trigger(
+            TriggerPayload::builder(
+                "testing",
+                TriggerRecipientsType::Single(
+                    TriggerRecipientBuilder::new("testing")
+                        .first_name("Test")
+                        .email("me+test@midka.dev")
+                        .build(),
+                ),
+            )
+            .payload(payload)
+            .overrides(TriggerOverrides {
+                email: Some(EmailOverrides {
+                    reply_to: Some("support@midka.dev".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .build(),
+        )
         .await;
 
     match result {
@@ -0,0 +1,228 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, punctuated::Punctuated, Attribute, Data, DeriveInput, Error,
+    Expr, ExprLit, Fields, Lit, LitStr, Meta, Token,
+};
+
+/// Implements `novu::NovuWorkflow` for a struct whose fields are the
+/// workflow's payload variables, and adds an inherent `trigger` method.
+///
+/// ```ignore
+/// #[derive(Serialize, NovuWorkflow)]
+/// #[novu(workflow = "payment-received")]
+/// struct PaymentReceived {
+///     amount: i64,
+///     currency: String,
+/// }
+///
+/// PaymentReceived { amount, currency }.trigger(&novu, to).await?;
+/// ```
+///
+/// Variable names follow `#[serde(rename)]`, `#[serde(rename_all)]` and
+/// `#[serde(skip)]`, so they match the serialized payload.
+#[proc_macro_derive(NovuWorkflow, attributes(novu))]
+pub fn derive_novu_workflow(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let workflow = workflow_id(&input)?;
+    let rename_all = serde_rename_all(&input.attrs)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "NovuWorkflow needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "NovuWorkflow can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut variables = Vec::new();
+    for field in fields {
+        let attrs = SerdeField::parse(&field.attrs)?;
+        if attrs.skip {
+            continue;
+        }
+
+        let name = match attrs.rename {
+            Some(name) => name,
+            None => {
+                let ident = field.ident.as_ref().expect("named field").to_string();
+                let ident = ident.strip_prefix("r#").unwrap_or(&ident).to_string();
+                match &rename_all {
+                    Some(rule) => apply_rename_rule(rule, &ident)
+                        .ok_or_else(|| Error::new(Span::call_site(), "unknown rename_all rule"))?,
+                    None => ident,
+                }
+            }
+        };
+        variables.push(name);
+    }
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(Self: ::novu::__private::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::novu::NovuWorkflow for #ident #ty_generics #where_clause {
+            const WORKFLOW_ID: &'static str = #workflow;
+            const VARIABLES: &'static [&'static str] = &[#(#variables),*];
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Triggers the workflow with `self` as the payload.
+            pub async fn trigger(
+                self,
+                novu: &::novu::Novu,
+                to: ::novu::events::TriggerRecipientsType,
+            ) -> ::std::result::Result<::novu::events::TriggerResponse, ::novu::error::NovuError> {
+                novu
+                    .trigger(::novu::NovuWorkflow::trigger_payload(self, to).build())
+                    .await
+            }
+        }
+    })
+}
+
+fn workflow_id(input: &DeriveInput) -> Result<LitStr, Error> {
+    let mut workflow = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("novu"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("workflow") {
+                workflow = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `workflow = \"...\"`"))
+            }
+        })?;
+    }
+
+    workflow.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "missing `#[novu(workflow = \"<workflow identifier>\")]`",
+        )
+    })
+}
+
+#[derive(Default)]
+struct SerdeField {
+    rename: Option<String>,
+    skip: bool,
+}
+
+impl SerdeField {
+    fn parse(attrs: &[Attribute]) -> Result<SerdeField, Error> {
+        let mut field = SerdeField::default();
+
+        for meta in serde_metas(attrs)? {
+            match &meta {
+                Meta::Path(path) if path.is_ident("skip") || path.is_ident("skip_serializing") => {
+                    field.skip = true;
+                }
+                Meta::NameValue(pair) if pair.path.is_ident("rename") => {
+                    field.rename = Some(string_value(&pair.value)?);
+                }
+                Meta::List(list) if list.path.is_ident("rename") => {
+                    list.parse_nested_meta(|nested| {
+                        if nested.path.is_ident("serialize") {
+                            field.rename = Some(nested.value()?.parse::<LitStr>()?.value());
+                        } else {
+                            nested.value()?.parse::<LitStr>()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(field)
+    }
+}
+
+fn serde_rename_all(attrs: &[Attribute]) -> Result<Option<String>, Error> {
+    for meta in serde_metas(attrs)? {
+        if let Meta::NameValue(pair) = &meta {
+            if pair.path.is_ident("rename_all") {
+                return string_value(&pair.value).map(Some);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn serde_metas(attrs: &[Attribute]) -> Result<Vec<Meta>, Error> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        metas.extend(nested);
+    }
+
+    Ok(metas)
+}
+
+fn string_value(expr: &Expr) -> Result<String, Error> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(lit.value()),
+        _ => Err(Error::new_spanned(expr, "expected a string literal")),
+    }
+}
+
+/// Mirrors serde's `rename_all` rules for snake_case field names.
+fn apply_rename_rule(rule: &str, field: &str) -> Option<String> {
+    let words = field.split('_').filter(|word| !word.is_empty());
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+
+    Some(match rule {
+        "lowercase" | "snake_case" => field.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_uppercase(),
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.replace('_', "-").to_uppercase(),
+        "PascalCase" => words.map(capitalize).collect(),
+        "camelCase" => {
+            let pascal: String = words.map(capitalize).collect();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        _ => return None,
+    })
+}
@@ -0,0 +1,96 @@
+use novu::{
+    events::{TriggerRecipientsType, TriggerResponse},
+    workflows::Trigger,
+    Novu, NovuWorkflow,
+};
+use serde::Serialize;
+
+#[derive(Serialize, NovuWorkflow)]
+#[novu(workflow = "payment-received")]
+struct PaymentReceived {
+    amount: i64,
+    currency: String,
+}
+
+#[derive(Serialize, NovuWorkflow)]
+#[serde(rename_all = "camelCase")]
+#[novu(workflow = "invoice-overdue")]
+struct InvoiceOverdue<T> {
+    invoice_id: String,
+    #[serde(rename = "total")]
+    amount_due: T,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    internal_note: String,
+}
+
+fn trigger_definition(variables: serde_json::Value) -> Trigger {
+    serde_json::from_value(serde_json::json!({
+        "type": "event",
+        "identifier": "payment-received",
+        "variables": variables,
+        "subscriberVariables": [],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(PaymentReceived::WORKFLOW_ID, "payment-received");
+    assert_eq!(PaymentReceived::VARIABLES, ["amount", "currency"]);
+
+    assert_eq!(InvoiceOverdue::<f64>::WORKFLOW_ID, "invoice-overdue");
+    assert_eq!(InvoiceOverdue::<f64>::VARIABLES, ["invoiceId", "total"]);
+}
+
+#[test]
+fn test_variables_against_workflow() {
+    let trigger = trigger_definition(serde_json::json!([
+        { "name": "amount" },
+        { "name": "payer.name" },
+        { "name": "payer.email" },
+    ]));
+
+    assert_eq!(PaymentReceived::missing_variables(&trigger), ["payer"]);
+    assert_eq!(PaymentReceived::unused_variables(&trigger), ["currency"]);
+}
+
+#[tokio::test]
+async fn test_trigger() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .and(body_json(serde_json::json!({
+            "name": "payment-received",
+            "to": "sub-1",
+            "payload": { "amount": 4200, "currency": "EUR" },
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_string(
+            r#"{"data":{"acknowledged":true,"status":"processed","transactionId":"txn"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = Novu::new("", Some(&server.uri())).unwrap();
+    let response: TriggerResponse = PaymentReceived {
+        amount: 4200,
+        currency: "EUR".to_string(),
+    }
+    .trigger(
+        &novu,
+        TriggerRecipientsType::builder()
+            .subscriber_id("sub-1")
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.transaction_id, "txn");
+}
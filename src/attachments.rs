@@ -0,0 +1,230 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{consts::ATTACHMENT_MAX_SIZE, error::NovuError, ChannelTypeEnum};
+
+/// A file sent along with a trigger payload, e.g. under its `attachments`
+/// key. `file` goes over the wire as base64.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub mime: String,
+    #[serde(with = "base64_bytes")]
+    pub file: Vec<u8>,
+    pub name: Option<String>,
+    /// Only send the attachment through these channels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channels: Option<Vec<ChannelTypeEnum>>,
+}
+
+enum AttachmentSource {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+    Reader(Box<dyn AsyncRead + Send + Unpin>),
+}
+
+/// Builds an [`Attachment`] from bytes, a file or an async reader.
+///
+/// The MIME type is guessed from the name's extension unless set, and
+/// content larger than [`ATTACHMENT_MAX_SIZE`] (or [`AttachmentBuilder::max_size`])
+/// is rejected without being read in full.
+pub struct AttachmentBuilder {
+    source: AttachmentSource,
+    name: Option<String>,
+    mime: Option<String>,
+    channels: Option<Vec<ChannelTypeEnum>>,
+    max_size: u64,
+}
+
+impl AttachmentBuilder {
+    fn new(source: AttachmentSource, name: Option<String>) -> AttachmentBuilder {
+        Self {
+            source,
+            name,
+            mime: None,
+            channels: None,
+            max_size: ATTACHMENT_MAX_SIZE,
+        }
+    }
+
+    pub fn from_bytes(name: impl ToString, bytes: impl Into<Vec<u8>>) -> AttachmentBuilder {
+        Self::new(
+            AttachmentSource::Bytes(bytes.into()),
+            Some(name.to_string()),
+        )
+    }
+
+    /// The file is read by [`AttachmentBuilder::build`], and named after the
+    /// file unless [`AttachmentBuilder::name`] is set.
+    pub fn from_path(path: impl Into<PathBuf>) -> AttachmentBuilder {
+        let path = path.into();
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+
+        Self::new(AttachmentSource::Path(path), name)
+    }
+
+    pub fn from_reader(
+        name: impl ToString,
+        reader: impl AsyncRead + Send + Unpin + 'static,
+    ) -> AttachmentBuilder {
+        Self::new(
+            AttachmentSource::Reader(Box::new(reader)),
+            Some(name.to_string()),
+        )
+    }
+
+    pub fn name(mut self, name: impl ToString) -> AttachmentBuilder {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn mime(mut self, mime: impl ToString) -> AttachmentBuilder {
+        self.mime = Some(mime.to_string());
+        self
+    }
+
+    pub fn channels(
+        mut self,
+        channels: impl IntoIterator<Item = ChannelTypeEnum>,
+    ) -> AttachmentBuilder {
+        self.channels = Some(channels.into_iter().collect());
+        self
+    }
+
+    /// In bytes.
+    pub fn max_size(mut self, max_size: u64) -> AttachmentBuilder {
+        self.max_size = max_size;
+        self
+    }
+
+    pub async fn build(self) -> Result<Attachment, NovuError> {
+        let name = self.name.unwrap_or_default();
+        let too_large = |size| NovuError::AttachmentTooLarge {
+            name: name.clone(),
+            size,
+            max_size: self.max_size,
+        };
+
+        let file = match self.source {
+            AttachmentSource::Bytes(bytes) => bytes,
+            AttachmentSource::Path(path) => {
+                let size = tokio::fs::metadata(&path).await?.len();
+                if size > self.max_size {
+                    return Err(too_large(size));
+                }
+                tokio::fs::read(&path).await?
+            }
+            AttachmentSource::Reader(reader) => {
+                // Reading one byte past the limit tells a full reader apart
+                // from one that is too large.
+                let mut bytes = Vec::new();
+                reader
+                    .take(self.max_size.saturating_add(1))
+                    .read_to_end(&mut bytes)
+                    .await?;
+                bytes
+            }
+        };
+
+        if file.len() as u64 > self.max_size {
+            return Err(too_large(file.len() as u64));
+        }
+
+        let mime = self.mime.unwrap_or_else(|| {
+            mime_guess::from_path(&name)
+                .first_or_octet_stream()
+                .to_string()
+        });
+
+        Ok(Attachment {
+            mime,
+            file,
+            name: (!name.is_empty()).then_some(name),
+            channels: self.channels,
+        })
+    }
+}
+
+mod base64_bytes {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        STANDARD.decode(encoded).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_attachment_wire_format() {
+    let attachment = AttachmentBuilder::from_bytes("invoice.pdf", b"%PDF-1.7".to_vec())
+        .channels([ChannelTypeEnum::EMAIL])
+        .build()
+        .await
+        .unwrap();
+
+    let json = serde_json::to_value(&attachment).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "mime": "application/pdf",
+            "file": "JVBERi0xLjc=",
+            "name": "invoice.pdf",
+            "channels": ["email"],
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<Attachment>(json).unwrap(),
+        attachment
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_attachment_sources_and_limits() {
+    let path = std::env::temp_dir().join(format!("novu-attachment-{}.csv", std::process::id()));
+    tokio::fs::write(&path, "id,amount\n1,42\n").await.unwrap();
+
+    let from_path = AttachmentBuilder::from_path(&path).build().await.unwrap();
+    assert_eq!(from_path.mime, "text/csv");
+    assert_eq!(from_path.file, b"id,amount\n1,42\n");
+
+    let too_large = AttachmentBuilder::from_path(&path)
+        .max_size(4)
+        .build()
+        .await;
+    assert!(matches!(
+        too_large,
+        Err(NovuError::AttachmentTooLarge { size: 15, .. })
+    ));
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    let from_reader = AttachmentBuilder::from_reader("notes", &b"plain"[..])
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(from_reader.mime, "application/octet-stream");
+    assert_eq!(from_reader.file, b"plain");
+
+    let too_large = AttachmentBuilder::from_reader("notes", &b"plain"[..])
+        .max_size(4)
+        .build()
+        .await;
+    assert!(matches!(
+        too_large,
+        Err(NovuError::AttachmentTooLarge { size: 5, .. })
+    ));
+
+    let missing = AttachmentBuilder::from_path("/does/not/exist.png")
+        .build()
+        .await;
+    assert!(matches!(missing, Err(NovuError::Io(_))));
+}
@@ -1,7 +1,4 @@
-use crate::{
-    client::{Client, Response},
-    error::NovuError,
-};
+use crate::{client::Client, error::NovuError, options::RequestOptions};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,6 +32,7 @@ pub struct BlueprintGroupByCategoryResponse {
     pub popular: Blueprint,
 }
 
+#[derive(Clone)]
 pub struct Blueprints {
     client: Client,
 }
@@ -44,28 +42,19 @@ impl Blueprints {
         Self { client }
     }
 
-    pub async fn group_by_category(&self) -> Result<BlueprintGroupByCategoryResponse, NovuError> {
-        let result: Response<BlueprintGroupByCategoryResponse> =
-            self.client.get("/blueprints/group-by-category").await?;
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
 
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+    pub async fn group_by_category(&self) -> Result<BlueprintGroupByCategoryResponse, NovuError> {
+        self.client.get("/blueprints/group-by-category").await
     }
 
     pub async fn template(&self, template_id: String) -> Result<Blueprint, NovuError> {
-        let result: Response<Blueprint> = self
-            .client
+        self.client
             .get(format!("/blueprints/{}", template_id))
-            .await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 }
 
@@ -0,0 +1,289 @@
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    client::{split_api_version, Client},
+    consts::NOVU_API_VERSION,
+    error::NovuError,
+    options::RequestOptions,
+    retry::RetryPolicy,
+    Novu,
+};
+
+/// Where the Novu API is hosted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Region {
+    #[default]
+    Us,
+    Eu,
+    /// The base url of a self-hosted instance, e.g.
+    /// `https://novu.example.com/api`. A trailing API version such as `/v1`
+    /// is used as the default [`NovuBuilder::api_version`].
+    SelfHosted(String),
+}
+
+impl Region {
+    pub fn base_url(&self) -> &str {
+        match self {
+            Region::Us => "https://api.novu.co",
+            Region::Eu => "https://eu.api.novu.co",
+            Region::SelfHosted(url) => url,
+        }
+    }
+}
+
+/// Configures a [`Novu`] client.
+///
+/// Timeouts, the proxy, the user agent, default headers and pool settings
+/// configure the `reqwest::Client` the builder creates. They are ignored when
+/// a client is supplied with [`NovuBuilder::http_client`].
+pub struct NovuBuilder {
+    api_key: String,
+    region: Region,
+    api_version: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    request_options: RequestOptions,
+    http_client: Option<reqwest::Client>,
+    validate_triggers: bool,
+}
+
+impl NovuBuilder {
+    pub fn new(api_key: impl ToString) -> NovuBuilder {
+        Self {
+            api_key: api_key.to_string(),
+            region: Region::default(),
+            api_version: None,
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            user_agent: None,
+            default_headers: HeaderMap::new(),
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            retry_policy: RetryPolicy::disabled(),
+            request_options: RequestOptions::default(),
+            http_client: None,
+            validate_triggers: false,
+        }
+    }
+
+    pub fn region(mut self, region: Region) -> NovuBuilder {
+        self.region = region;
+        self
+    }
+
+    /// Shorthand for `region(Region::SelfHosted(url))`.
+    pub fn api_url(self, url: impl ToString) -> NovuBuilder {
+        self.region(Region::SelfHosted(url.to_string()))
+    }
+
+    /// Defaults to the version the base url ends in, if any, or
+    /// [`NOVU_API_VERSION`].
+    pub fn api_version(mut self, api_version: impl ToString) -> NovuBuilder {
+        self.api_version = Some(api_version.to_string());
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> NovuBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> NovuBuilder {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> NovuBuilder {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl ToString) -> NovuBuilder {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    pub fn default_header(
+        mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<NovuBuilder, NovuError> {
+        let name = HeaderName::from_bytes(name.as_ref().as_bytes())
+            .map_err(|_| NovuError::BuildError(format!("header name '{}'", name.as_ref())))?;
+
+        self.default_headers
+            .insert(name, HeaderValue::from_str(value.as_ref())?);
+        Ok(self)
+    }
+
+    pub fn default_headers(mut self, headers: HeaderMap) -> NovuBuilder {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> NovuBuilder {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> NovuBuilder {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> NovuBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Options applied to every request, e.g. to derive idempotency keys
+    /// from request payloads.
+    pub fn request_options(mut self, options: RequestOptions) -> NovuBuilder {
+        self.request_options = options;
+        self
+    }
+
+    pub fn http_client(mut self, client: reqwest::Client) -> NovuBuilder {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// See [`Novu::with_trigger_validation`].
+    pub fn validate_triggers(mut self, validate: bool) -> NovuBuilder {
+        self.validate_triggers = validate;
+        self
+    }
+
+    pub fn build(self) -> Result<Novu, NovuError> {
+        let http_client = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder().default_headers(self.default_headers);
+
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                if let Some(max) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max);
+                }
+                if let Some(timeout) = self.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(timeout);
+                }
+
+                builder
+                    .build()
+                    .map_err(|_| NovuError::BuildError("client".to_string()))?
+            }
+        };
+
+        let base_url = self.region.base_url();
+        let api_version = self.api_version.unwrap_or_else(|| {
+            split_api_version(base_url)
+                .1
+                .unwrap_or(NOVU_API_VERSION)
+                .to_string()
+        });
+
+        let client = Client::with_http_client(self.api_key, base_url, api_version, http_client)?
+            .with_retry_policy(self.retry_policy)
+            .with_options(self.request_options);
+
+        let novu = Novu::from_client(client);
+        match self.validate_triggers {
+            true => Ok(novu.with_trigger_validation()),
+            false => Ok(novu),
+        }
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_builder_configures_requests() {
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v2/environments/me"))
+        .and(header("Authorization", "ApiKey secret"))
+        .and(header("User-Agent", "payments/1.0"))
+        .and(header("X-Team", "billing"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = NovuBuilder::new("secret")
+        .api_url(format!("{}/", server.uri()))
+        .api_version("v2")
+        .user_agent("payments/1.0")
+        .default_header("X-Team", "billing")
+        .unwrap()
+        .timeout(Duration::from_secs(5))
+        .connect_timeout(Duration::from_secs(1))
+        .pool_max_idle_per_host(4)
+        .build()
+        .unwrap();
+
+    let result = novu.current_environment().await;
+    assert!(matches!(result, Err(NovuError::Unauthorized(_))));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_builder_accepts_http_client() {
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/environments/me"))
+        .and(header("Authorization", "ApiKey secret"))
+        .respond_with(ResponseTemplate::new(401))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = NovuBuilder::new("secret")
+        .region(Region::SelfHosted(server.uri()))
+        .http_client(reqwest::Client::new())
+        .build()
+        .unwrap();
+
+    let result = novu.current_environment().await;
+    assert!(matches!(result, Err(NovuError::Unauthorized(_))));
+}
+
+#[cfg(test)]
+#[test]
+fn test_region_base_urls() {
+    assert_eq!(Region::default(), Region::Us);
+    assert_eq!(Region::Us.base_url(), "https://api.novu.co");
+    assert_eq!(Region::Eu.base_url(), "https://eu.api.novu.co");
+    assert_eq!(
+        Region::SelfHosted("https://novu.example.com".to_string()).base_url(),
+        "https://novu.example.com"
+    );
+}
@@ -1,20 +1,23 @@
 use crate::{
-    client::{Client, Response},
+    client::Client,
     error::NovuError,
-    utils::generate_query_string,
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::{generate_query_string, string_enum},
 };
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum ChangeType {
-    Feed,
-    MessageTemplate,
-    Layout,
-    DefaultLayout,
-    NotificationTemplate,
-    NotificationGroup,
+string_enum! {
+    pub enum ChangeType {
+        Feed => "Feed",
+        MessageTemplate => "MessageTemplate",
+        Layout => "Layout",
+        DefaultLayout => "DefaultLayout",
+        NotificationTemplate => "NotificationTemplate",
+        NotificationGroup => "NotificationGroup",
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,14 +36,7 @@ pub struct Change {
     pub change_type: ChangeType,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ChangesResponse {
-    pub page: u32,
-    pub total_count: u32,
-    pub page_size: u32,
-    pub data: Vec<Change>,
-}
+pub type ChangesResponse = Page<Change>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -60,6 +56,7 @@ pub struct ApplyChangeResponse {
     pub data: Vec<Change>,
 }
 
+#[derive(Clone)]
 pub struct Changes {
     client: Client,
 }
@@ -69,6 +66,11 @@ impl Changes {
         Self { client }
     }
 
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
     pub async fn list(
         &self,
         page: Option<u32>,
@@ -86,53 +88,40 @@ impl Changes {
             }),
         );
 
-        let result: Response<ChangesResponse> = self
-            .client
-            .get(format!("/changes/?{}", generate_query_string(&params)))
-            .await?;
+        self.client
+            .get_paginated(format!("/changes/?{}", generate_query_string(&params)))
+            .await
+    }
 
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+    /// Streams every change, fetching pages as the stream is polled.
+    pub fn list_all(
+        &self,
+        promoted: bool,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<Change, NovuError>> {
+        let changes = self.clone();
+
+        paginate(options, move |page| {
+            let changes = changes.clone();
+            async move { changes.list(Some(page), options.page_size, promoted).await }
+        })
     }
 
     pub async fn count(&self) -> Result<ChangesCountResponse, NovuError> {
-        let result: Response<ChangesCountResponse> = self.client.get("/changes/count").await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.get("/changes/count").await
     }
 
     pub async fn bulk_apply(
         &self,
         data: BulkApplyChangesRequest,
     ) -> Result<ApplyChangeResponse, NovuError> {
-        let result: Response<ApplyChangeResponse> =
-            self.client.post("/changes/bulk/apply", Some(&data)).await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.post("/changes/bulk/apply", Some(&data)).await
     }
 
     pub async fn apply(&self, change_id: String) -> Result<ApplyChangeResponse, NovuError> {
-        let result: Response<ApplyChangeResponse> = self
-            .client
+        self.client
             .post(format!("/changes/{}/apply", change_id), None::<&()>)
-            .await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 }
 
@@ -144,3 +133,19 @@ async fn test_list_changes() {
     let result = changes.list(None, Some(10), false).await;
     assert!(result.is_err());
 }
+
+#[cfg(test)]
+#[test]
+fn test_change_type_round_trip() {
+    crate::utils::assert_string_enum(
+        ChangeType::ALL,
+        &[
+            "Feed",
+            "MessageTemplate",
+            "Layout",
+            "DefaultLayout",
+            "NotificationTemplate",
+            "NotificationGroup",
+        ],
+    );
+}
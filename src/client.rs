@@ -1,6 +1,14 @@
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::{consts::NOVU_API_VERSION, error::NovuError};
+use crate::{
+    builder::Region,
+    consts::NOVU_API_VERSION,
+    error::NovuError,
+    options::{IdempotencyKey, RequestOptions},
+    pagination::Page,
+    retry::{retry_after, RetryPolicy},
+};
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -23,6 +31,8 @@ pub struct DataContainer<T> {
     pub data: T,
 }
 
+/// The raw shapes a Novu response could take.
+#[deprecated(note = "the client decodes responses itself; match on `Result<T, NovuError>` instead")]
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
 pub enum Response<T> {
@@ -31,10 +41,20 @@ pub enum Response<T> {
     Messages(ApiErrorWithMessages),
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum ErrorResponse {
+    Error(ApiError),
+    Messages(ApiErrorWithMessages),
+}
+
 #[derive(Clone)]
 pub struct Client {
     api_url: String,
+    api_key: HeaderValue,
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
+    options: RequestOptions,
 }
 
 impl Client {
@@ -42,148 +62,785 @@ impl Client {
         api_key: impl ToString,
         backend_url: Option<impl ToString>,
     ) -> Result<Self, NovuError> {
+        let backend_url = backend_url
+            .map(|url| url.to_string())
+            .unwrap_or_else(|| Region::Us.base_url().to_string());
+        let api_version = split_api_version(&backend_url)
+            .1
+            .unwrap_or(NOVU_API_VERSION)
+            .to_string();
+
+        Self::with_http_client(api_key, backend_url, api_version, Self::build_client()?)
+    }
+
+    /// Builds a client on top of an already configured `reqwest::Client`.
+    /// The API key is sent with every request, so `client` doesn't need to
+    /// carry it.
+    pub fn with_http_client(
+        api_key: impl ToString,
+        backend_url: impl ToString,
+        api_version: impl ToString,
+        client: reqwest::Client,
+    ) -> Result<Self, NovuError> {
+        let mut api_key = HeaderValue::from_str(&format!("ApiKey {}", api_key.to_string()))?;
+        api_key.set_sensitive(true);
+
+        let backend_url = backend_url.to_string();
+        let api_version = api_version.to_string();
+        let (base_url, url_version) = split_api_version(&backend_url);
+        if let Some(url_version) = url_version.filter(|version| *version != api_version) {
+            return Err(NovuError::ConfigError {
+                key: "api_url".to_string(),
+                reason: format!(
+                    "'{}' ends in API version '{}', but '{}' was requested",
+                    backend_url, url_version, api_version
+                ),
+            });
+        }
+
         Ok(Self {
-            api_url: Self::build_backend_url(&backend_url),
-            client: Self::build_client(&api_key)?,
+            api_url: Self::build_backend_url(base_url, &api_version),
+            api_key,
+            client,
+            retry_policy: RetryPolicy::disabled(),
+            options: RequestOptions::default(),
         })
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Applies `options` to every request sent through this client.
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     pub async fn post<T: DeserializeOwned>(
         &self,
         endpoint: impl ToString,
         data: Option<&impl Serialize>,
-    ) -> Result<Response<T>, NovuError> {
-        if data.is_none() {
-            let res = self.client.post(self.get_url(endpoint)).send().await;
+    ) -> Result<T, NovuError> {
+        let endpoint = endpoint.to_string();
+        let mut request = self.client.post(self.get_url(&endpoint));
 
-            match res {
-                Ok(response) => Ok(response.json::<Response<T>>().await?),
-                Err(err) => Err(NovuError::HttpError(err)),
-            }
-        } else {
-            let res = self
-                .client
-                .post(self.get_url(endpoint))
-                .json(&data.unwrap())
-                .send()
-                .await;
-
-            match res {
-                Ok(response) => Ok(response.json::<Response<T>>().await?),
-                Err(err) => Err(NovuError::HttpError(err)),
-            }
+        if let Some(data) = data {
+            request = request.json(data);
         }
+
+        self.send(&endpoint, request).await
     }
 
-    pub async fn get<T: DeserializeOwned>(
-        &self,
-        endpoint: impl ToString,
-    ) -> Result<Response<T>, NovuError> {
-        let res = self.client.get(self.get_url(endpoint)).send().await;
+    pub async fn get<T: DeserializeOwned>(&self, endpoint: impl ToString) -> Result<T, NovuError> {
+        let endpoint = endpoint.to_string();
+        let request = self.client.get(self.get_url(&endpoint));
 
-        match res {
-            Ok(response) => Ok(response.json::<Response<T>>().await?),
-            Err(err) => Err(NovuError::HttpError(err)),
-        }
+        self.send(&endpoint, request).await
     }
 
     pub async fn delete<T: DeserializeOwned>(
         &self,
         endpoint: impl ToString,
-    ) -> Result<Response<T>, NovuError> {
-        let res = self.client.delete(self.get_url(endpoint)).send().await;
+    ) -> Result<T, NovuError> {
+        let endpoint = endpoint.to_string();
+        let request = self.client.delete(self.get_url(&endpoint));
 
-        match res {
-            Ok(response) => Ok(response.json::<Response<T>>().await?),
-            Err(err) => Err(NovuError::HttpError(err)),
-        }
+        self.send(&endpoint, request).await
     }
 
     pub async fn put<T: DeserializeOwned>(
         &self,
         endpoint: impl ToString,
         data: &impl Serialize,
-    ) -> Result<Response<T>, NovuError> {
-        let res = self
-            .client
-            .put(self.get_url(endpoint))
-            .json(&data)
-            .send()
-            .await;
+    ) -> Result<T, NovuError> {
+        let endpoint = endpoint.to_string();
+        let request = self.client.put(self.get_url(&endpoint)).json(data);
 
-        match res {
-            Ok(response) => Ok(response.json::<Response<T>>().await?),
-            Err(err) => Err(NovuError::HttpError(err)),
-        }
+        self.send(&endpoint, request).await
     }
 
     pub async fn patch<T: DeserializeOwned>(
         &self,
         endpoint: impl ToString,
         data: Option<&impl Serialize>,
-    ) -> Result<Response<T>, NovuError> {
-        if data.is_none() {
-            let res = self.client.patch(self.get_url(endpoint)).send().await;
+    ) -> Result<T, NovuError> {
+        let endpoint = endpoint.to_string();
+        let mut request = self.client.patch(self.get_url(&endpoint));
 
-            match res {
-                Ok(response) => Ok(response.json::<Response<T>>().await?),
-                Err(err) => Err(NovuError::HttpError(err)),
-            }
-        } else {
-            let res = self
-                .client
-                .patch(self.get_url(endpoint))
-                .json(&data.unwrap())
-                .send()
-                .await;
-
-            match res {
-                Ok(response) => Ok(response.json::<Response<T>>().await?),
-                Err(err) => Err(NovuError::HttpError(err)),
-            }
+        if let Some(data) = data {
+            request = request.json(data);
         }
+
+        self.send(&endpoint, request).await
     }
 
-    fn get_url(&self, endpoint: impl ToString) -> String {
-        format!("{}{}", self.api_url, endpoint.to_string())
+    /// Fetches one page of a paginated list. Novu returns these without the
+    /// `data` envelope used by other endpoints.
+    pub async fn get_paginated<T: DeserializeOwned>(
+        &self,
+        endpoint: impl ToString,
+    ) -> Result<Page<T>, NovuError> {
+        let endpoint = endpoint.to_string();
+        let request = self.client.get(self.get_url(&endpoint));
+
+        self.send_with(&endpoint, request, decode_page).await
     }
 
-    fn build_backend_url(backend_url: &Option<impl ToString>) -> String {
-        if let Some(backend_url) = backend_url {
-            let backend_url = &backend_url.to_string();
+    async fn send<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, NovuError> {
+        self.send_with(endpoint, request, decode_response).await
+    }
 
-            if backend_url.contains("novu.co/v") {
-                return backend_url.to_string();
-            }
+    async fn send_with<T>(
+        &self,
+        endpoint: &str,
+        request: reqwest::RequestBuilder,
+        decode: fn(&str, u16, &HeaderMap, &[u8]) -> Result<T, NovuError>,
+    ) -> Result<T, NovuError> {
+        let mut request = request.build()?;
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, self.api_key.clone());
+        self.options.apply(&mut request)?;
 
-            return format!("{}/{}", backend_url, NOVU_API_VERSION);
-        }
+        let retryable = self
+            .retry_policy
+            .allows_retry(request.method(), request.headers());
+        let mut attempt = 1;
 
-        format!("https://api.novu.co/{}", NOVU_API_VERSION)
-    }
+        loop {
+            let retry = retryable && attempt < self.retry_policy.max_attempts();
+            let current = request
+                .try_clone()
+                .ok_or_else(|| NovuError::BuildError("request".to_string()))?;
+
+            let delay = match self.client.execute(current).await {
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    let headers = response.headers().clone();
+                    let body = response.bytes().await?;
+
+                    let delay = (retry && self.retry_policy.is_retryable_status(status))
+                        .then(|| self.retry_policy.delay(attempt, Some(&headers)))
+                        .flatten();
 
-    fn build_client(api_key: &impl ToString) -> Result<reqwest::Client, NovuError> {
-        let mut default_headers = reqwest::header::HeaderMap::new();
+                    match delay {
+                        Some(delay) => delay,
+                        None => return decode(endpoint, status, &headers, &body),
+                    }
+                }
+                Err(err) => {
+                    let err = NovuError::HttpError(err);
+
+                    if !(retry && self.retry_policy.is_retryable_error(&err)) {
+                        return Err(err);
+                    }
+
+                    self.retry_policy.delay(attempt, None).ok_or(err)?
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
 
-        default_headers.insert(
-            "Authorization",
-            reqwest::header::HeaderValue::from_str(&format!("ApiKey {}", &api_key.to_string()))?,
-        );
+    fn get_url(&self, endpoint: impl ToString) -> String {
+        format!("{}{}", self.api_url, endpoint.to_string())
+    }
 
-        let client = reqwest::Client::builder()
-            .default_headers(default_headers)
-            .build();
+    fn build_backend_url(backend_url: &str, api_version: &str) -> String {
+        format!("{}/{}", backend_url.trim_end_matches('/'), api_version)
+    }
 
-        match client {
+    fn build_client() -> Result<reqwest::Client, NovuError> {
+        match reqwest::Client::builder().build() {
             Ok(data) => Ok(data),
             Err(_) => Err(NovuError::BuildError("client".to_string())),
         }
     }
 
+    /// Clients for the `parts` requests one logical call is split into. An
+    /// explicit idempotency key is suffixed with the index of each part, so
+    /// that Novu doesn't take parts 1..n for replays of the first one.
+    pub(crate) fn split_idempotency_key(&self, parts: usize) -> Vec<Client> {
+        match &self.options.idempotency_key {
+            Some(IdempotencyKey::Explicit(key)) if parts > 1 => (0..parts)
+                .map(|index| {
+                    self.clone_client().with_options(
+                        self.options
+                            .with_idempotency_key(format!("{}-{}", key, index)),
+                    )
+                })
+                .collect(),
+            _ => (0..parts).map(|_| self.clone_client()).collect(),
+        }
+    }
+
     pub fn clone_client(&self) -> Self {
         Self {
             api_url: self.api_url.clone(),
+            api_key: self.api_key.clone(),
             client: self.client.clone(),
+            retry_policy: self.retry_policy.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+/// Splits a trailing API version segment, e.g. the `/v1` of
+/// `https://api.novu.co/v1`, off `url`.
+pub(crate) fn split_api_version(url: &str) -> (&str, Option<&str>) {
+    let url = url.trim_end_matches('/');
+
+    match url.rsplit_once('/') {
+        Some((base, segment))
+            if segment.len() > 1
+                && segment.starts_with('v')
+                && segment[1..].bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            (base, Some(segment))
+        }
+        _ => (url, None),
+    }
+}
+
+/// Turns a raw Novu response into either the `data` it carries or the
+/// [`NovuError`] matching its status code.
+///
+/// The status is checked before the body is looked at, so that no-content
+/// responses and non-JSON error pages (e.g. an HTML 502 from a proxy) don't
+/// surface as opaque decode errors.
+pub(crate) fn decode_response<T: DeserializeOwned>(
+    endpoint: &str,
+    status: u16,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<T, NovuError> {
+    check_status(endpoint, status, headers, body)?;
+
+    let decode_error = |source| decode_error(endpoint, status, headers, body, source);
+
+    if status == 204 || is_blank(body) {
+        return serde_json::from_slice(b"null").map_err(decode_error);
+    }
+
+    serde_json::from_slice::<DataContainer<T>>(body)
+        .map(|container| container.data)
+        .map_err(|source| {
+            error_body(endpoint, status, headers, body).unwrap_or_else(|| decode_error(source))
+        })
+}
+
+/// Like [`decode_response`], for paginated lists that aren't wrapped in a
+/// `data` envelope.
+pub(crate) fn decode_page<T: DeserializeOwned>(
+    endpoint: &str,
+    status: u16,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<Page<T>, NovuError> {
+    check_status(endpoint, status, headers, body)?;
+
+    serde_json::from_slice(body).map_err(|source| {
+        error_body(endpoint, status, headers, body)
+            .unwrap_or_else(|| decode_error(endpoint, status, headers, body, source))
+    })
+}
+
+fn check_status(
+    endpoint: &str,
+    status: u16,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<(), NovuError> {
+    if (200..300).contains(&status) {
+        return Ok(());
+    }
+
+    let messages = match serde_json::from_slice::<ErrorResponse>(body) {
+        Ok(ErrorResponse::Error(err)) => vec![err.message],
+        Ok(ErrorResponse::Messages(err)) => err.message,
+        Err(_) if is_blank(body) => Vec::new(),
+        Err(_) => vec![String::from_utf8_lossy(body).into_owned()],
+    };
+
+    Err(NovuError::from_status(
+        endpoint,
+        status,
+        messages,
+        retry_after(headers),
+    ))
+}
+
+/// Novu occasionally answers with a successful status but an error body,
+/// in which case the `statusCode` from the body decides the error.
+fn error_body(endpoint: &str, status: u16, headers: &HeaderMap, body: &[u8]) -> Option<NovuError> {
+    let (status_code, messages) = match serde_json::from_slice::<ErrorResponse>(body).ok()? {
+        ErrorResponse::Error(err) => (err.status_code, vec![err.message]),
+        ErrorResponse::Messages(err) => (err.status_code, err.message),
+    };
+
+    Some(NovuError::from_status(
+        endpoint,
+        u16::try_from(status_code).unwrap_or(status),
+        messages,
+        retry_after(headers),
+    ))
+}
+
+fn decode_error(
+    endpoint: &str,
+    status: u16,
+    headers: &HeaderMap,
+    body: &[u8],
+    source: serde_json::Error,
+) -> NovuError {
+    NovuError::DecodeError {
+        path: endpoint.to_string(),
+        status,
+        body: String::from_utf8_lossy(body).into_owned(),
+        headers: Box::new(headers.clone()),
+        source,
+    }
+}
+
+fn is_blank(body: &[u8]) -> bool {
+    body.iter().all(u8::is_ascii_whitespace)
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_success() {
+    let result: Result<String, NovuError> =
+        decode_response("/feeds", 200, &HeaderMap::new(), br#"{"data":"feed"}"#);
+    assert_eq!(result.unwrap(), "feed");
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_validation_messages() {
+    let body = br#"{"statusCode":400,"message":["name should not be empty","name must be a string"],"error":"Bad Request"}"#;
+
+    for status in [400, 422] {
+        match decode_response::<()>("/feeds", status, &HeaderMap::new(), body) {
+            Err(NovuError::Validation { path, messages }) => {
+                assert_eq!(path, "/feeds");
+                assert_eq!(
+                    messages,
+                    vec!["name should not be empty", "name must be a string"]
+                );
+            }
+            other => panic!("unexpected result for {}: {:?}", status, other),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_unauthorized() {
+    let body = br#"{"statusCode":401,"message":"Unauthorized"}"#;
+
+    for status in [401, 403] {
+        match decode_response::<()>("/environments/me", status, &HeaderMap::new(), body) {
+            Err(NovuError::Unauthorized(path)) => assert_eq!(path, "/environments/me"),
+            other => panic!("unexpected result for {}: {:?}", status, other),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_not_found() {
+    let body = br#"{"statusCode":404,"message":"Layout not found","error":"Not Found"}"#;
+
+    match decode_response::<()>("/layouts/1", 404, &HeaderMap::new(), body) {
+        Err(NovuError::NotFound { path, message }) => {
+            assert_eq!(path, "/layouts/1");
+            assert_eq!(message, "Layout not found");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_conflict() {
+    let body = br#"{"statusCode":409,"message":"Feed already exists","error":"Conflict"}"#;
+
+    match decode_response::<()>("/feeds", 409, &HeaderMap::new(), body) {
+        Err(NovuError::Conflict { message, .. }) => assert_eq!(message, "Feed already exists"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_rate_limited() {
+    let body = br#"{"statusCode":429,"message":"ThrottlerException: Too Many Requests"}"#;
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        reqwest::header::RETRY_AFTER,
+        reqwest::header::HeaderValue::from_static("3"),
+    );
+
+    match decode_response::<()>("/events/trigger", 429, &headers, body) {
+        Err(NovuError::RateLimited { path, retry_after }) => {
+            assert_eq!(path, "/events/trigger");
+            assert_eq!(retry_after, Some(std::time::Duration::from_secs(3)));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_server_error() {
+    let body = br#"{"statusCode":500,"message":"Internal server error"}"#;
+
+    for status in [500, 502, 503] {
+        match decode_response::<()>("/workflows", status, &HeaderMap::new(), body) {
+            Err(NovuError::ServerError { code, message, .. }) => {
+                assert_eq!(code, status);
+                assert_eq!(message, "Internal server error");
+            }
+            other => panic!("unexpected result for {}: {:?}", status, other),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_unexpected_status() {
+    let body = br#"{"statusCode":418,"message":"I'm a teapot"}"#;
+
+    match decode_response::<()>("/feeds", 418, &HeaderMap::new(), body) {
+        Err(NovuError::UnexpectedResponse { msg, code }) => {
+            assert_eq!(msg, "I'm a teapot");
+            assert_eq!(code, "418");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_no_content() {
+    let result: Result<(), NovuError> =
+        decode_response("/layouts/1/default", 204, &HeaderMap::new(), b"");
+    assert!(result.is_ok());
+
+    let result: Result<(), NovuError> =
+        decode_response("/messages/1", 200, &HeaderMap::new(), b"  ");
+    assert!(result.is_ok());
+
+    let result: Result<Option<String>, NovuError> =
+        decode_response("/feeds", 200, &HeaderMap::new(), b"");
+    assert_eq!(result.unwrap(), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_empty_body_for_required_data() {
+    match decode_response::<String>("/feeds", 204, &HeaderMap::new(), b"") {
+        Err(NovuError::DecodeError { status, body, .. }) => {
+            assert_eq!(status, 204);
+            assert!(body.is_empty());
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_html_error_page() {
+    let body = b"<html><body><h1>502 Bad Gateway</h1></body></html>";
+
+    match decode_response::<String>("/events/trigger", 502, &HeaderMap::new(), body) {
+        Err(NovuError::ServerError { code, message, .. }) => {
+            assert_eq!(code, 502);
+            assert_eq!(message, String::from_utf8_lossy(body));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_malformed_success_keeps_raw_response() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "x-request-id",
+        reqwest::header::HeaderValue::from_static("abc123"),
+    );
+    let body = br#"{"unexpected":true}"#;
+
+    match decode_response::<String>("/feeds", 200, &headers, body) {
+        Err(NovuError::DecodeError {
+            path,
+            status,
+            body,
+            headers,
+            ..
+        }) => {
+            assert_eq!(path, "/feeds");
+            assert_eq!(status, 200);
+            assert_eq!(body, r#"{"unexpected":true}"#);
+            assert_eq!(headers.get("x-request-id").unwrap(), "abc123");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[cfg(test)]
+fn test_retry_policy() -> RetryPolicy {
+    RetryPolicy::builder()
+        .max_attempts(3)
+        .base_delay(std::time::Duration::from_millis(1))
+        .build()
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_retries_transient_statuses() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/feeds"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/feeds"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":"feed"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy());
+
+    let result: String = client.get("/feeds").await.unwrap();
+    assert_eq!(result, "feed");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/layouts/1"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy());
+
+    let result: Result<(), NovuError> = client.delete("/layouts/1").await;
+    assert!(matches!(
+        result,
+        Err(NovuError::ServerError { code: 502, .. })
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_does_not_retry_post_without_idempotency_key() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .respond_with(ResponseTemplate::new(503))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy());
+
+    let result: Result<(), NovuError> = client.post("/events/trigger", None::<&()>).await;
+    assert!(matches!(
+        result,
+        Err(NovuError::ServerError { code: 503, .. })
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_does_not_retry_unlisted_statuses() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/feeds"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy());
+
+    let result: Result<(), NovuError> = client.get("/feeds").await;
+    assert!(matches!(
+        result,
+        Err(NovuError::ServerError { code: 500, .. })
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_rate_limit_hint_beyond_cap_is_not_retried() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/feeds"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "120"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy());
+
+    match client.get::<()>("/feeds").await {
+        Err(NovuError::RateLimited { retry_after, .. }) => {
+            assert_eq!(retry_after, Some(std::time::Duration::from_secs(120)))
         }
+        other => panic!("unexpected result: {:?}", other),
     }
 }
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_rate_limit_reset_is_honoured() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/feeds"))
+        .respond_with(ResponseTemplate::new(429).insert_header("RateLimit-Reset", "0"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/feeds"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":"feed"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy());
+
+    let result: String = client.get("/feeds").await.unwrap();
+    assert_eq!(result, "feed");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_retries_post_with_idempotency_key() {
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .and(header("Idempotency-Key", "job-42"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .and(header("Idempotency-Key", "job-42"))
+        .respond_with(ResponseTemplate::new(201).set_body_string(r#"{"data":"sent"}"#))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri()))
+        .unwrap()
+        .with_retry_policy(test_retry_policy())
+        .with_options(RequestOptions::builder().idempotency_key("job-42").build());
+
+    let result: String = client.post("/events/trigger", None::<&()>).await.unwrap();
+    assert_eq!(result, "sent");
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_error_body_with_success_status() {
+    let body = br#"{"statusCode":404,"message":"Layout not found","error":"Not Found"}"#;
+
+    match decode_response::<String>("/layouts/1", 200, &HeaderMap::new(), body) {
+        Err(NovuError::NotFound { path, message }) => {
+            assert_eq!(path, "/layouts/1");
+            assert_eq!(message, "Layout not found");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    let page = decode_page::<String>("/layouts", 200, &HeaderMap::new(), body);
+    assert!(matches!(page, Err(NovuError::NotFound { .. })));
+}
+
+#[cfg(test)]
+#[test]
+fn test_backend_url_with_api_version() {
+    let client = Client::new("", Some("https://api.novu.co/v1")).unwrap();
+    assert_eq!(client.api_url, "https://api.novu.co/v1");
+
+    let client = Client::new("", Some("https://novu.example.com/api/v2/")).unwrap();
+    assert_eq!(client.api_url, "https://novu.example.com/api/v2");
+
+    let client = Client::new("", Some("https://novu.example.com/vault")).unwrap();
+    assert_eq!(client.api_url, "https://novu.example.com/vault/v1");
+
+    let err = Client::with_http_client("", "https://api.novu.co/v1", "v2", reqwest::Client::new())
+        .err()
+        .unwrap();
+    assert!(matches!(err, NovuError::ConfigError { key, .. } if key == "api_url"));
+}
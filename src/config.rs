@@ -0,0 +1,375 @@
+use std::{path::Path, time::Duration};
+
+use serde::Deserialize;
+
+use crate::{
+    builder::{NovuBuilder, Region},
+    error::NovuError,
+    retry::RetryPolicy,
+};
+
+/// Settings for building a [`crate::Novu`] client, read from the environment
+/// or from a TOML/JSON file.
+///
+/// | env var                    | file key              |
+/// |----------------------------|-----------------------|
+/// | `NOVU_API_KEY`             | `api_key`             |
+/// | `NOVU_API_URL`             | `api_url`             |
+/// | `NOVU_REGION`              | `region`              |
+/// | `NOVU_API_VERSION`         | `api_version`         |
+/// | `NOVU_TIMEOUT_MS`          | `timeout_ms`          |
+/// | `NOVU_CONNECT_TIMEOUT_MS`  | `connect_timeout_ms`  |
+/// | `NOVU_RETRY_MAX_ATTEMPTS`  | `retry.max_attempts`  |
+/// | `NOVU_RETRY_BASE_DELAY_MS` | `retry.base_delay_ms` |
+/// | `NOVU_RETRY_MAX_DELAY_MS`  | `retry.max_delay_ms`  |
+/// | `NOVU_RETRY_JITTER`        | `retry.jitter`        |
+///
+/// The region is `us`, `eu` or `self-hosted`. It defaults to `self-hosted`
+/// when an api url is given and to `us` otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NovuConfig {
+    pub api_key: String,
+    pub region: Region,
+    pub api_version: Option<String>,
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    pub retry: Option<RetryConfig>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RetryConfig {
+    pub max_attempts: Option<u32>,
+    pub base_delay_ms: Option<u64>,
+    pub max_delay_ms: Option<u64>,
+    pub jitter: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    api_key: Option<String>,
+    api_url: Option<String>,
+    region: Option<String>,
+    api_version: Option<String>,
+    timeout_ms: Option<u64>,
+    connect_timeout_ms: Option<u64>,
+    retry: Option<RetryConfig>,
+}
+
+impl NovuConfig {
+    pub fn from_env() -> Result<Self, NovuError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Reads a `.toml` or `.json` file, picking the format from the extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, NovuError> {
+        let path = path.as_ref();
+        let parse = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml_str,
+            Some("json") => Self::from_json_str,
+            _ => {
+                return Err(NovuError::ConfigError {
+                    key: path.display().to_string(),
+                    reason: "expected a .toml or .json file".to_string(),
+                })
+            }
+        };
+
+        let contents = std::fs::read_to_string(path).map_err(|err| NovuError::ConfigError {
+            key: path.display().to_string(),
+            reason: err.to_string(),
+        })?;
+
+        parse(&contents)
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self, NovuError> {
+        let file: FileConfig = toml::from_str(contents).map_err(|err| NovuError::ConfigError {
+            key: "toml".to_string(),
+            reason: err.to_string(),
+        })?;
+
+        Self::from_file_config(file)
+    }
+
+    pub fn from_json_str(contents: &str) -> Result<Self, NovuError> {
+        let file: FileConfig =
+            serde_json::from_str(contents).map_err(|err| NovuError::ConfigError {
+                key: "json".to_string(),
+                reason: err.to_string(),
+            })?;
+
+        Self::from_file_config(file)
+    }
+
+    pub(crate) fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, NovuError> {
+        let get = |key: &str| lookup(key).filter(|value| !value.trim().is_empty());
+
+        let retry = RetryConfig {
+            max_attempts: parse_env(&get, "NOVU_RETRY_MAX_ATTEMPTS")?,
+            base_delay_ms: parse_env(&get, "NOVU_RETRY_BASE_DELAY_MS")?,
+            max_delay_ms: parse_env(&get, "NOVU_RETRY_MAX_DELAY_MS")?,
+            jitter: parse_env(&get, "NOVU_RETRY_JITTER")?,
+        };
+
+        Ok(Self {
+            api_key: get("NOVU_API_KEY").ok_or_else(|| missing("NOVU_API_KEY"))?,
+            region: parse_region(
+                "NOVU_REGION",
+                get("NOVU_REGION"),
+                "NOVU_API_URL",
+                get("NOVU_API_URL"),
+            )?,
+            api_version: get("NOVU_API_VERSION"),
+            timeout: parse_env(&get, "NOVU_TIMEOUT_MS")?.map(Duration::from_millis),
+            connect_timeout: parse_env(&get, "NOVU_CONNECT_TIMEOUT_MS")?.map(Duration::from_millis),
+            retry: (retry != RetryConfig::default()).then_some(retry),
+        })
+    }
+
+    fn from_file_config(file: FileConfig) -> Result<Self, NovuError> {
+        Ok(Self {
+            api_key: file
+                .api_key
+                .filter(|key| !key.trim().is_empty())
+                .ok_or_else(|| missing("api_key"))?,
+            region: parse_region("region", file.region, "api_url", file.api_url)?,
+            api_version: file.api_version,
+            timeout: file.timeout_ms.map(Duration::from_millis),
+            connect_timeout: file.connect_timeout_ms.map(Duration::from_millis),
+            retry: file.retry,
+        })
+    }
+
+    /// A builder with these settings applied, for further customization.
+    pub fn into_builder(self) -> NovuBuilder {
+        let mut builder = NovuBuilder::new(self.api_key).region(self.region);
+
+        if let Some(api_version) = self.api_version {
+            builder = builder.api_version(api_version);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(retry) = self.retry {
+            builder = builder.retry_policy(retry.into_policy());
+        }
+
+        builder
+    }
+}
+
+impl RetryConfig {
+    pub fn into_policy(self) -> RetryPolicy {
+        let mut builder = RetryPolicy::builder();
+
+        if let Some(max_attempts) = self.max_attempts {
+            builder = builder.max_attempts(max_attempts);
+        }
+        if let Some(base_delay) = self.base_delay_ms {
+            builder = builder.base_delay(Duration::from_millis(base_delay));
+        }
+        if let Some(max_delay) = self.max_delay_ms {
+            builder = builder.max_delay(Duration::from_millis(max_delay));
+        }
+        if let Some(jitter) = self.jitter {
+            builder = builder.jitter(jitter);
+        }
+
+        builder.build()
+    }
+}
+
+fn missing(key: &str) -> NovuError {
+    NovuError::ConfigError {
+        key: key.to_string(),
+        reason: "missing".to_string(),
+    }
+}
+
+fn parse_env<T: std::str::FromStr>(
+    get: &impl Fn(&str) -> Option<String>,
+    key: &str,
+) -> Result<Option<T>, NovuError> {
+    get(key)
+        .map(|value| {
+            value.trim().parse().map_err(|_| NovuError::ConfigError {
+                key: key.to_string(),
+                reason: format!("couldn't parse '{}'", value),
+            })
+        })
+        .transpose()
+}
+
+fn parse_region(
+    region_key: &str,
+    region: Option<String>,
+    url_key: &str,
+    url: Option<String>,
+) -> Result<Region, NovuError> {
+    let region = region.map(|region| region.trim().to_lowercase());
+
+    match (region.as_deref(), url) {
+        (None | Some("self-hosted") | Some("self_hosted"), Some(url)) => {
+            Ok(Region::SelfHosted(url))
+        }
+        (Some("self-hosted") | Some("self_hosted"), None) => Err(NovuError::ConfigError {
+            key: url_key.to_string(),
+            reason: "required for a self-hosted region".to_string(),
+        }),
+        (None | Some("us"), None) => Ok(Region::Us),
+        (Some("eu"), None) => Ok(Region::Eu),
+        (Some("us") | Some("eu"), Some(_)) => Err(NovuError::ConfigError {
+            key: url_key.to_string(),
+            reason: format!(
+                "can't be combined with {} '{}'",
+                region_key,
+                region.unwrap_or_default()
+            ),
+        }),
+        (Some(other), _) => Err(NovuError::ConfigError {
+            key: region_key.to_string(),
+            reason: format!("expected 'us', 'eu' or 'self-hosted', got '{}'", other),
+        }),
+    }
+}
+
+#[cfg(test)]
+fn lookup<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+    move |key| {
+        vars.iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_config_from_env() {
+    let config = NovuConfig::from_lookup(lookup(&[
+        ("NOVU_API_KEY", "secret"),
+        ("NOVU_REGION", "EU"),
+        ("NOVU_TIMEOUT_MS", "2500"),
+        ("NOVU_RETRY_MAX_ATTEMPTS", "4"),
+        ("NOVU_RETRY_JITTER", "false"),
+    ]))
+    .unwrap();
+
+    assert_eq!(config.api_key, "secret");
+    assert_eq!(config.region, Region::Eu);
+    assert_eq!(config.timeout, Some(Duration::from_millis(2500)));
+    assert_eq!(config.connect_timeout, None);
+    assert_eq!(
+        config.retry,
+        Some(RetryConfig {
+            max_attempts: Some(4),
+            jitter: Some(false),
+            ..RetryConfig::default()
+        })
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_config_from_env_errors() {
+    let err = NovuConfig::from_lookup(lookup(&[])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid configuration for 'NOVU_API_KEY': missing"
+    );
+
+    let err = NovuConfig::from_lookup(lookup(&[
+        ("NOVU_API_KEY", "secret"),
+        ("NOVU_TIMEOUT_MS", "5s"),
+    ]))
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid configuration for 'NOVU_TIMEOUT_MS': couldn't parse '5s'"
+    );
+
+    let err = NovuConfig::from_lookup(lookup(&[
+        ("NOVU_API_KEY", "secret"),
+        ("NOVU_REGION", "asia"),
+    ]))
+    .unwrap_err();
+    assert!(matches!(err, NovuError::ConfigError { key, .. } if key == "NOVU_REGION"));
+
+    let err = NovuConfig::from_lookup(lookup(&[
+        ("NOVU_API_KEY", "secret"),
+        ("NOVU_REGION", "self-hosted"),
+    ]))
+    .unwrap_err();
+    assert!(matches!(err, NovuError::ConfigError { key, .. } if key == "NOVU_API_URL"));
+}
+
+#[cfg(test)]
+#[test]
+fn test_config_from_env_self_hosted() {
+    let config = NovuConfig::from_lookup(lookup(&[
+        ("NOVU_API_KEY", "secret"),
+        ("NOVU_API_URL", "https://novu.example.com"),
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        config.region,
+        Region::SelfHosted("https://novu.example.com".to_string())
+    );
+    assert_eq!(config.retry, None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_config_from_files() {
+    let toml = NovuConfig::from_toml_str(
+        r#"
+        api_key = "secret"
+        region = "eu"
+        connect_timeout_ms = 500
+
+        [retry]
+        max_attempts = 5
+        base_delay_ms = 200
+        "#,
+    )
+    .unwrap();
+
+    let json = NovuConfig::from_json_str(
+        r#"{
+            "api_key": "secret",
+            "region": "eu",
+            "connect_timeout_ms": 500,
+            "retry": { "max_attempts": 5, "base_delay_ms": 200 }
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(toml, json);
+    assert_eq!(toml.region, Region::Eu);
+    assert_eq!(toml.connect_timeout, Some(Duration::from_millis(500)));
+    assert_eq!(toml.retry.unwrap().max_attempts, Some(5));
+}
+
+#[cfg(test)]
+#[test]
+fn test_config_from_files_errors() {
+    let err = NovuConfig::from_toml_str(r#"region = "eu""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid configuration for 'api_key': missing"
+    );
+
+    let err = NovuConfig::from_toml_str("api_key = \"secret\"\ntimeout_ms = \"slow\"").unwrap_err();
+    assert!(matches!(err, NovuError::ConfigError { key, .. } if key == "toml"));
+
+    let err = NovuConfig::from_json_str(r#"{"api_key": "secret", "retries": 3}"#).unwrap_err();
+    assert!(err.to_string().contains("unknown field `retries`"));
+
+    let err = NovuConfig::from_file("novu.yaml").unwrap_err();
+    assert!(matches!(err, NovuError::ConfigError { key, .. } if key == "novu.yaml"));
+}
@@ -1 +1,17 @@
+use std::time::Duration;
+
 pub const NOVU_API_VERSION: &str = "v1";
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// The most events Novu accepts in one `/events/trigger/bulk` request.
+pub const TRIGGER_BULK_MAX_EVENTS: usize = 100;
+/// How many bulk requests `Novu::trigger_bulk` keeps in flight.
+pub const TRIGGER_BULK_CONCURRENCY: usize = 4;
+/// The most subscribers Novu accepts in one `/subscribers/bulk` request.
+pub const SUBSCRIBER_BULK_MAX_SUBSCRIBERS: usize = 500;
+/// How many bulk requests `Subscribers::bulk_create` keeps in flight.
+pub const SUBSCRIBER_BULK_CONCURRENCY: usize = 4;
+/// How long `TriggerValidator` trusts its list of workflows before an unknown
+/// identifier makes it fetch the list again.
+pub const TRIGGER_VALIDATOR_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// The largest attachment `AttachmentBuilder` accepts unless told otherwise.
+pub const ATTACHMENT_MAX_SIZE: u64 = 20 * 1024 * 1024;
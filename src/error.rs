@@ -1,4 +1,6 @@
-use reqwest::header::InvalidHeaderValue;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, InvalidHeaderValue};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -7,18 +9,97 @@ pub enum NovuError {
     BuildError(String),
     #[error("http error: {0}")]
     HttpError(#[from] reqwest::Error),
-    #[error("failed to deserialize {0} response")]
-    DeserializeError(String),
+    #[error("failed to decode {path} response with status {status}: {source}")]
+    DecodeError {
+        path: String,
+        status: u16,
+        body: String,
+        headers: Box<HeaderMap>,
+        source: serde_json::Error,
+    },
     #[error("invalid api key")]
     InvalidHeaderValue(#[from] InvalidHeaderValue),
     #[error("couldn't trigger '{0}'")]
     TriggerError(String),
     #[error("unauthorized, path: {0}")]
-    UnauthorizedError(String),
+    Unauthorized(String),
+    #[error("not found, path: {path}: {message}")]
+    NotFound { path: String, message: String },
+    #[error("conflict, path: {path}: {message}")]
+    Conflict { path: String, message: String },
+    #[error("validation failed, path: {path}: {}", .messages.join("; "))]
+    Validation { path: String, messages: Vec<String> },
+    #[error("rate limited, path: {path}")]
+    RateLimited {
+        path: String,
+        retry_after: Option<Duration>,
+    },
+    #[error("server error {code}, path: {path}: {message}")]
+    ServerError {
+        path: String,
+        code: u16,
+        message: String,
+    },
+    #[error("invalid configuration for '{key}': {reason}")]
+    ConfigError { key: String, reason: String },
     #[error("invalid values when '{0}': {1}")]
     InvalidValues(String, String),
+    #[error(
+        "invalid payload for '{workflow}', missing: [{}], wrong type: [{}]",
+        .missing.join(", "),
+        .mistyped.join(", ")
+    )]
+    InvalidPayload {
+        workflow: String,
+        missing: Vec<String>,
+        mistyped: Vec<String>,
+    },
+    #[error("attachment '{name}' is {size} bytes, more than the {max_size} allowed")]
+    AttachmentTooLarge {
+        name: String,
+        size: u64,
+        max_size: u64,
+    },
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("couldn't find template '{0}'")]
     TemplateNotFound(String),
     #[error("NovuError - UnexpectedResponse: {code:?} - {msg:?}")]
     UnexpectedResponse { msg: String, code: String },
 }
+
+impl NovuError {
+    /// Maps a non-successful status code and the messages Novu sent along
+    /// with it onto the matching error variant.
+    pub fn from_status(
+        path: impl ToString,
+        code: u16,
+        messages: Vec<String>,
+        retry_after: Option<Duration>,
+    ) -> Self {
+        let path = path.to_string();
+
+        match code {
+            401 | 403 => NovuError::Unauthorized(path),
+            404 => NovuError::NotFound {
+                path,
+                message: messages.join("; "),
+            },
+            409 => NovuError::Conflict {
+                path,
+                message: messages.join("; "),
+            },
+            400 | 422 => NovuError::Validation { path, messages },
+            429 => NovuError::RateLimited { path, retry_after },
+            500..=599 => NovuError::ServerError {
+                path,
+                code,
+                message: messages.join("; "),
+            },
+            code => NovuError::UnexpectedResponse {
+                msg: messages.join("; "),
+                code: code.to_string(),
+            },
+        }
+    }
+}
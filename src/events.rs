@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use serde::{Deserialize, Serialize};
 
-use crate::IAttachmentOptions;
+use crate::{error::NovuError, tenants::TenantIdentifier, utils::string_enum, IAttachmentOptions};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -19,11 +19,308 @@ pub enum AllowedPayloadValues {
 
 pub type ITriggerPayload = HashMap<String, AllowedPayloadValues>;
 
+/// Replaces workflow and integration settings for a single trigger.
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<EmailOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sms: Option<SmsOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fcm: Option<FcmOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apns: Option<ApnsOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat: Option<ChatOverrides>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cc: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bcc: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The layout to render the email with instead of the workflow's.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration_identifier: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SmsOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// The sender id or number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration_identifier: Option<String>,
+}
+
+string_enum! {
+    pub enum FcmMessageType {
+        Notification => "notification",
+        Data => "data",
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FcmOverrides {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub message_type: Option<FcmMessageType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sound: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub click_action: Option<String>,
+    /// FCM only accepts string values in the data payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApnsOverrides {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alert: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sound: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collapse_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// Seconds since the Unix epoch after which the notification is dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mutable_content: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_available: Option<bool>,
+    /// Custom keys added next to the `aps` dictionary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<HashMap<String, serde_json::Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatOverrides {
+    /// Sends the message to this webhook instead of the subscriber's.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integration_identifier: Option<String>,
+}
+
+/// Who the notification appears to come from, e.g. for avatars in the feed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TriggerActor {
+    SubscriberId(String),
+    Subscriber(TriggerRecipient),
+}
+
+/// The tenant the workflow runs for, either an existing tenant's identifier
+/// or a tenant that is created or updated on the fly.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TriggerTenant {
+    Identifier(TenantIdentifier),
+    Tenant(TriggerTenantPayload),
+}
+
+impl From<TenantIdentifier> for TriggerTenant {
+    fn from(identifier: TenantIdentifier) -> Self {
+        TriggerTenant::Identifier(identifier)
+    }
+}
+
+impl From<TriggerTenantPayload> for TriggerTenant {
+    fn from(tenant: TriggerTenantPayload) -> Self {
+        TriggerTenant::Tenant(tenant)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerTenantPayload {
+    pub identifier: TenantIdentifier,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<HashMap<String, serde_json::Value>>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum TriggerRecipientsType {
     Single(TriggerRecipient),
     Multiple(Vec<TriggerRecipient>),
+    SubscriberId(String),
+    Topic(TriggerTopic),
+    Mixed(Vec<TriggerTarget>),
+}
+
+impl TriggerRecipientsType {
+    pub fn builder() -> TriggerRecipientsBuilder {
+        TriggerRecipientsBuilder::default()
+    }
+}
+
+/// Every subscriber of the topic, serialized as `{"type": "Topic", "topicKey": ..}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename = "Topic", rename_all = "camelCase")]
+pub struct TriggerTopic {
+    pub topic_key: String,
+}
+
+impl TriggerTopic {
+    pub fn new(topic_key: impl ToString) -> TriggerTopic {
+        Self {
+            topic_key: topic_key.to_string(),
+        }
+    }
+}
+
+/// One entry of a mixed recipients list.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TriggerTarget {
+    Subscriber(TriggerRecipient),
+    SubscriberId(String),
+    Topic(TriggerTopic),
+}
+
+impl From<TriggerRecipient> for TriggerTarget {
+    fn from(recipient: TriggerRecipient) -> Self {
+        TriggerTarget::Subscriber(recipient)
+    }
+}
+
+impl From<TriggerTopic> for TriggerTarget {
+    fn from(topic: TriggerTopic) -> Self {
+        TriggerTarget::Topic(topic)
+    }
+}
+
+impl From<String> for TriggerTarget {
+    fn from(subscriber_id: String) -> Self {
+        TriggerTarget::SubscriberId(subscriber_id)
+    }
+}
+
+impl From<&str> for TriggerTarget {
+    fn from(subscriber_id: &str) -> Self {
+        TriggerTarget::SubscriberId(subscriber_id.to_string())
+    }
+}
+
+/// Collects subscribers, subscriber ids and topics into the most specific
+/// [`TriggerRecipientsType`] that holds them.
+#[derive(Default)]
+pub struct TriggerRecipientsBuilder {
+    targets: Vec<TriggerTarget>,
+}
+
+impl TriggerRecipientsBuilder {
+    pub fn new() -> TriggerRecipientsBuilder {
+        Self::default()
+    }
+
+    pub fn subscriber(mut self, recipient: TriggerRecipient) -> TriggerRecipientsBuilder {
+        self.targets.push(TriggerTarget::Subscriber(recipient));
+        self
+    }
+
+    pub fn subscriber_id(mut self, subscriber_id: impl ToString) -> TriggerRecipientsBuilder {
+        self.targets
+            .push(TriggerTarget::SubscriberId(subscriber_id.to_string()));
+        self
+    }
+
+    pub fn topic(mut self, topic_key: impl ToString) -> TriggerRecipientsBuilder {
+        self.targets
+            .push(TriggerTarget::Topic(TriggerTopic::new(topic_key)));
+        self
+    }
+
+    pub fn target(mut self, target: impl Into<TriggerTarget>) -> TriggerRecipientsBuilder {
+        self.targets.push(target.into());
+        self
+    }
+
+    /// Fails when no subscriber or topic was added, since Novu rejects
+    /// triggers without recipients.
+    pub fn build(mut self) -> Result<TriggerRecipientsType, NovuError> {
+        if self.targets.is_empty() {
+            return Err(NovuError::BuildError(
+                "trigger recipients without a subscriber or topic".to_string(),
+            ));
+        }
+
+        if self.targets.len() == 1 {
+            return Ok(match self.targets.remove(0) {
+                TriggerTarget::Subscriber(recipient) => TriggerRecipientsType::Single(recipient),
+                TriggerTarget::SubscriberId(id) => TriggerRecipientsType::SubscriberId(id),
+                TriggerTarget::Topic(topic) => TriggerRecipientsType::Topic(topic),
+            });
+        }
+
+        if self
+            .targets
+            .iter()
+            .all(|target| matches!(target, TriggerTarget::Subscriber(_)))
+        {
+            return Ok(TriggerRecipientsType::Multiple(
+                self.targets
+                    .into_iter()
+                    .filter_map(|target| match target {
+                        TriggerTarget::Subscriber(recipient) => Some(recipient),
+                        _ => None,
+                    })
+                    .collect(),
+            ));
+        }
+
+        Ok(TriggerRecipientsType::Mixed(self.targets))
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
@@ -99,11 +396,100 @@ impl TriggerRecipientBuilder {
     }
 }
 
+/// `T` is any serializable payload, e.g. a struct describing the variables of
+/// one workflow. [`ITriggerPayload`] covers payloads built at runtime.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct TriggerPayload {
-    pub payload: ITriggerPayload,
+#[serde(rename_all = "camelCase")]
+pub struct TriggerPayload<T = ITriggerPayload> {
+    pub payload: T,
     pub to: TriggerRecipientsType,
     pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<TriggerOverrides>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<TriggerActor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<TriggerTenant>,
+    /// Reusing a transaction id makes Novu ignore the trigger as a duplicate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+}
+
+impl TriggerPayload {
+    pub fn builder(name: impl ToString, to: TriggerRecipientsType) -> TriggerPayloadBuilder {
+        TriggerPayloadBuilder::new(name, to)
+    }
+}
+
+pub struct TriggerPayloadBuilder<T = ITriggerPayload> {
+    payload: TriggerPayload<T>,
+}
+
+impl TriggerPayloadBuilder {
+    pub fn new(name: impl ToString, to: TriggerRecipientsType) -> TriggerPayloadBuilder {
+        Self {
+            payload: TriggerPayload {
+                payload: HashMap::new(),
+                to,
+                name: name.to_string(),
+                overrides: None,
+                actor: None,
+                tenant: None,
+                transaction_id: None,
+            },
+        }
+    }
+}
+
+impl<T> TriggerPayloadBuilder<T> {
+    /// Replaces the payload, possibly with one of a different type.
+    pub fn payload<U>(self, payload: U) -> TriggerPayloadBuilder<U> {
+        let TriggerPayload {
+            to,
+            name,
+            overrides,
+            actor,
+            tenant,
+            transaction_id,
+            ..
+        } = self.payload;
+
+        TriggerPayloadBuilder {
+            payload: TriggerPayload {
+                payload,
+                to,
+                name,
+                overrides,
+                actor,
+                tenant,
+                transaction_id,
+            },
+        }
+    }
+
+    pub fn overrides(mut self, overrides: TriggerOverrides) -> TriggerPayloadBuilder<T> {
+        self.payload.overrides = Some(overrides);
+        self
+    }
+
+    pub fn actor(mut self, actor: TriggerActor) -> TriggerPayloadBuilder<T> {
+        self.payload.actor = Some(actor);
+        self
+    }
+
+    pub fn tenant(mut self, tenant: impl Into<TriggerTenant>) -> TriggerPayloadBuilder<T> {
+        self.payload.tenant = Some(tenant.into());
+        self
+    }
+
+    pub fn transaction_id(mut self, transaction_id: impl ToString) -> TriggerPayloadBuilder<T> {
+        self.payload.transaction_id = Some(transaction_id.to_string());
+        self
+    }
+
+    pub fn build(self) -> TriggerPayload<T> {
+        self.payload
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -111,5 +497,254 @@ pub struct TriggerPayload {
 pub struct TriggerResponse {
     pub acknowledged: bool,
     pub status: String,
+    #[serde(default)]
     pub transaction_id: String,
+    /// Why Novu rejected the event, if it did.
+    pub error: Option<Vec<String>>,
+}
+
+impl TriggerResponse {
+    pub fn is_rejected(&self) -> bool {
+        !self.acknowledged || self.error.as_ref().map_or(false, |error| !error.is_empty())
+    }
+}
+
+/// Triggers a workflow for every subscriber in the environment.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastPayload<T = ITriggerPayload> {
+    pub name: String,
+    pub payload: T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<TriggerOverrides>,
+}
+
+/// Novu acknowledges a broadcast the same way as a single trigger.
+pub type BroadcastResponse = TriggerResponse;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BulkTriggerPayload<T> {
+    pub events: Vec<TriggerPayload<T>>,
+}
+
+#[derive(Debug)]
+pub enum BulkTriggerError {
+    /// Novu received the event but did not trigger it.
+    Rejected(TriggerResponse),
+    /// The request carrying the event failed. The error is shared by every
+    /// event sent in the same batch.
+    Request(Arc<NovuError>),
+    /// Trigger validation rejected the event, so it was not sent.
+    Invalid(NovuError),
+}
+
+/// The outcome of [`crate::Novu::trigger_bulk`].
+#[derive(Debug)]
+pub struct BulkTriggerResponse {
+    /// One entry per event, in the order the events were given.
+    pub results: Vec<Result<TriggerResponse, BulkTriggerError>>,
+}
+
+impl BulkTriggerResponse {
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+
+    /// The index and error of every event that was not triggered.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &BulkTriggerError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.as_ref().err().map(|err| (index, err)))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_serialize_recipient_shapes() {
+    use serde_json::json;
+
+    let to_json = |to: TriggerRecipientsType| serde_json::to_value(to).unwrap();
+
+    assert_eq!(
+        to_json(
+            TriggerRecipientsType::builder()
+                .subscriber(
+                    TriggerRecipientBuilder::new("sub-1")
+                        .email("a@b.co")
+                        .build()
+                )
+                .build()
+                .unwrap()
+        ),
+        json!({
+            "subscriberId": "sub-1",
+            "email": "a@b.co",
+            "firstName": null,
+            "lastName": null,
+            "phoneNumber": null,
+            "avatarUrl": null,
+        })
+    );
+    assert_eq!(
+        to_json(
+            TriggerRecipientsType::builder()
+                .subscriber_id("sub-1")
+                .build()
+                .unwrap()
+        ),
+        json!("sub-1")
+    );
+    assert_eq!(
+        to_json(
+            TriggerRecipientsType::builder()
+                .topic("beta-users")
+                .build()
+                .unwrap()
+        ),
+        json!({ "type": "Topic", "topicKey": "beta-users" })
+    );
+    assert!(matches!(
+        TriggerRecipientsType::builder()
+            .subscriber(TriggerRecipientBuilder::new("sub-1").build())
+            .subscriber(TriggerRecipientBuilder::new("sub-2").build())
+            .build()
+            .unwrap(),
+        TriggerRecipientsType::Multiple(_)
+    ));
+    assert_eq!(
+        to_json(
+            TriggerRecipientsType::builder()
+                .topic("beta-users")
+                .subscriber_id("sub-1")
+                .target("sub-2")
+                .build()
+                .unwrap()
+        ),
+        json!([{ "type": "Topic", "topicKey": "beta-users" }, "sub-1", "sub-2"])
+    );
+    assert!(matches!(
+        TriggerRecipientsType::builder().build(),
+        Err(NovuError::BuildError(_))
+    ));
+}
+
+#[cfg(test)]
+#[test]
+fn test_deserialize_recipient_shapes() {
+    let from_json = |json: &str| serde_json::from_str::<TriggerRecipientsType>(json).unwrap();
+
+    assert!(matches!(
+        from_json(r#"{"subscriberId":"sub-1"}"#),
+        TriggerRecipientsType::Single(_)
+    ));
+    assert!(matches!(
+        from_json(r#""sub-1""#),
+        TriggerRecipientsType::SubscriberId(id) if id == "sub-1"
+    ));
+    assert!(matches!(
+        from_json(r#"{"type":"Topic","topicKey":"beta-users"}"#),
+        TriggerRecipientsType::Topic(topic) if topic == TriggerTopic::new("beta-users")
+    ));
+    assert!(matches!(
+        from_json(r#"[{"subscriberId":"sub-1"},{"subscriberId":"sub-2"}]"#),
+        TriggerRecipientsType::Multiple(recipients) if recipients.len() == 2
+    ));
+
+    match from_json(
+        r#"[{"type":"Topic","topicKey":"beta-users"},"sub-1",{"subscriberId":"sub-2"}]"#,
+    ) {
+        TriggerRecipientsType::Mixed(targets) => {
+            assert!(matches!(targets[0], TriggerTarget::Topic(_)));
+            assert!(matches!(targets[1], TriggerTarget::SubscriberId(_)));
+            assert!(matches!(targets[2], TriggerTarget::Subscriber(_)));
+        }
+        other => panic!("expected mixed recipients, got {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_serialize_trigger_payload_fields() {
+    use serde_json::json;
+
+    let payload = TriggerPayload::builder(
+        "invoice-paid",
+        TriggerRecipientsType::builder()
+            .subscriber_id("sub-1")
+            .build()
+            .unwrap(),
+    )
+    .overrides(TriggerOverrides {
+        email: Some(EmailOverrides {
+            from: Some("billing@brand-a.com".to_string()),
+            reply_to: Some("support@brand-a.com".to_string()),
+            layout_identifier: Some("brand-a".to_string()),
+            ..Default::default()
+        }),
+        sms: Some(SmsOverrides {
+            from: Some("BRAND A".to_string()),
+            ..Default::default()
+        }),
+        fcm: Some(FcmOverrides {
+            message_type: Some(FcmMessageType::Data),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })
+    .actor(TriggerActor::SubscriberId("sub-admin".to_string()))
+    .tenant(TenantIdentifier::new("brand-a"))
+    .transaction_id("invoice-42")
+    .build();
+
+    assert_eq!(
+        serde_json::to_value(&payload).unwrap(),
+        json!({
+            "name": "invoice-paid",
+            "payload": {},
+            "to": "sub-1",
+            "overrides": {
+                "email": {
+                    "from": "billing@brand-a.com",
+                    "replyTo": "support@brand-a.com",
+                    "layoutIdentifier": "brand-a",
+                },
+                "sms": { "from": "BRAND A" },
+                "fcm": { "type": "data" },
+            },
+            "actor": "sub-admin",
+            "tenant": "brand-a",
+            "transactionId": "invoice-42",
+        })
+    );
+
+    let minimal = TriggerPayload::builder(
+        "invoice-paid",
+        TriggerRecipientsType::builder()
+            .subscriber_id("sub-1")
+            .build()
+            .unwrap(),
+    )
+    .tenant(TriggerTenantPayload {
+        identifier: "brand-b".into(),
+        name: Some("Brand B".to_string()),
+        data: None,
+    })
+    .build();
+
+    assert_eq!(
+        serde_json::to_value(&minimal).unwrap(),
+        json!({
+            "name": "invoice-paid",
+            "payload": {},
+            "to": "sub-1",
+            "tenant": { "identifier": "brand-b", "name": "Brand B" },
+        })
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_fcm_message_type_round_trip() {
+    crate::utils::assert_string_enum(FcmMessageType::ALL, &["notification", "data"]);
 }
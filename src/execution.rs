@@ -1,37 +1,194 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    client::{Client, Response},
+    client::Client,
     error::NovuError,
+    options::RequestOptions,
+    utils::{generate_query_string, string_enum},
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+string_enum! {
+    /// The kind of workflow step a job ran.
+    pub enum StepType {
+        InApp => "in_app",
+        Email => "email",
+        Sms => "sms",
+        Chat => "chat",
+        Push => "push",
+        Digest => "digest",
+        Trigger => "trigger",
+        Delay => "delay",
+        Custom => "custom",
+    }
+}
+
+string_enum! {
+    pub enum ExecutionDetailStatus {
+        Success => "Success",
+        Warning => "Warning",
+        Failed => "Failed",
+        Pending => "Pending",
+        Queued => "Queued",
+        ReadConfirmation => "ReadConfirmation",
+    }
+}
+
+string_enum! {
+    /// Where the information in an execution detail came from.
+    pub enum ExecutionDetailSource {
+        Credentials => "Credentials",
+        Internal => "Internal",
+        Payload => "Payload",
+        Webhook => "Webhook",
+    }
+}
+
+/// One step of delivering a notification, e.g. "Message sent" or a
+/// provider error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ExecutionsResponse {
-    pub data: Vec<String>,
+pub struct ExecutionDetail {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_jobId")]
+    pub job_id: String,
+    #[serde(rename = "_notificationId")]
+    pub notification_id: String,
+    #[serde(rename = "_notificationTemplateId")]
+    pub notification_template_id: Option<String>,
+    #[serde(rename = "_subscriberId")]
+    pub subscriber_id: String,
+    #[serde(rename = "_messageId")]
+    pub message_id: Option<String>,
+    pub transaction_id: String,
+    /// The type of the job the detail belongs to.
+    pub channel: Option<StepType>,
+    pub provider_id: Option<String>,
+    /// A human readable summary, e.g. "Message sent".
+    pub detail: String,
+    pub source: ExecutionDetailSource,
+    pub status: ExecutionDetailStatus,
+    /// What the provider answered, usually JSON encoded.
+    pub raw: Option<String>,
+    #[serde(default)]
+    pub is_test: bool,
+    #[serde(default)]
+    pub is_retry: bool,
+    pub created_at: String,
 }
 
-pub struct Executions {
+#[derive(Clone)]
+pub struct ExecutionDetails {
     client: Client,
 }
 
-impl Executions {
+impl ExecutionDetails {
     pub fn new(client: Client) -> Self {
         Self { client }
     }
 
-    pub async fn list(&self, notification_id: String, subscriber_id: String) -> Result<ExecutionsResponse, NovuError> {
-        let result: Response<ExecutionsResponse> = self
-            .client
-            .get(format!("/execution-details", page))
-            .await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => todo!("{:?}", err),
-            crate::client::Response::Messages(err) => todo!("{:?}", err),
-        }
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
     }
 
-} 
+    /// The execution details of one notification for one subscriber.
+    pub async fn list(
+        &self,
+        notification_id: impl ToString,
+        subscriber_id: impl ToString,
+    ) -> Result<Vec<ExecutionDetail>, NovuError> {
+        let mut params = HashMap::new();
+        params.insert("notificationId", Some(notification_id.to_string()));
+        params.insert("subscriberId", Some(subscriber_id.to_string()));
 
+        self.client
+            .get(format!(
+                "/execution-details?{}",
+                generate_query_string(&params)
+            ))
+            .await
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_list_execution_details() {
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/execution-details"))
+        .and(query_param("notificationId", "notification-1"))
+        .and(query_param("subscriberId", "sub-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [{
+                "_id": "detail-1",
+                "_jobId": "job-1",
+                "_notificationId": "notification-1",
+                "_notificationTemplateId": "workflow-1",
+                "_subscriberId": "subscriber-1",
+                "_environmentId": "env-1",
+                "transactionId": "txn",
+                "channel": "sms",
+                "providerId": "twilio",
+                "detail": "Unexpected provider error",
+                "source": "Internal",
+                "status": "Failed",
+                "raw": "{\"code\":21211}",
+                "isTest": false,
+                "isRetry": false,
+                "createdAt": "2023-10-01T12:00:00.000Z",
+            }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let details = novu
+        .execution_details
+        .list("notification-1", "sub-1")
+        .await
+        .unwrap();
+
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].channel, Some(StepType::Sms));
+    assert_eq!(details[0].status, ExecutionDetailStatus::Failed);
+    assert_eq!(details[0].source, ExecutionDetailSource::Internal);
+    assert_eq!(details[0].provider_id.as_deref(), Some("twilio"));
+    assert_eq!(details[0].raw.as_deref(), Some(r#"{"code":21211}"#));
+}
+
+#[cfg(test)]
+#[test]
+fn test_execution_enums_round_trip() {
+    use crate::utils::assert_string_enum;
+
+    assert_string_enum(
+        StepType::ALL,
+        &[
+            "in_app", "email", "sms", "chat", "push", "digest", "trigger", "delay", "custom",
+        ],
+    );
+    assert_string_enum(
+        ExecutionDetailStatus::ALL,
+        &[
+            "Success",
+            "Warning",
+            "Failed",
+            "Pending",
+            "Queued",
+            "ReadConfirmation",
+        ],
+    );
+    assert_string_enum(
+        ExecutionDetailSource::ALL,
+        &["Credentials", "Internal", "Payload", "Webhook"],
+    );
+}
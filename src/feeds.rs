@@ -1,7 +1,4 @@
-use crate::{
-    client::{Client, Response},
-    error::NovuError,
-};
+use crate::{client::Client, error::NovuError, options::RequestOptions};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -35,57 +32,20 @@ impl Feeds {
         Self { client }
     }
 
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
     pub async fn list(&self) -> Result<Vec<Feed>, NovuError> {
-        let result = self.client.get("/feeds").await?;
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                400 => {
-                    println!("{:?}", err);
-                    todo!()
-                }
-                401 => Err(NovuError::UnauthorizedError("/feeds".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.get("/feeds").await
     }
 
     pub async fn create(&self, data: FeedPayload) -> Result<Feed, NovuError> {
-        let result: Response<Feed> = self.client.post("/feeds", Some(&data)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/feeds".to_string())),
-                409 => {
-                    println!("Feed already exists");
-                    todo!()
-                }
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.post("/feeds", Some(&data)).await
     }
 
     pub async fn delete(&self, data: DeleteFeedPayload) -> Result<Vec<Feed>, NovuError> {
-        let result = self.client.delete(&format!("/feeds/{}", data._id)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                400 => {
-                    println!("Could not find feed with id {}", data._id);
-                    todo!()
-                }
-                401 => Err(NovuError::UnauthorizedError("/feeds".to_string())),
-                404 => {
-                    println!("Feed not found");
-                    todo!()
-                }
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.delete(&format!("/feeds/{}", data._id)).await
     }
 }
@@ -1,9 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    client::{Client, Response},
-    error::NovuError,
-    ChannelTypeEnum,
+    client::Client, error::NovuError, options::RequestOptions, utils::string_enum, ChannelTypeEnum,
 };
 
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -66,24 +64,24 @@ pub struct StepFilter {
     children: Vec<FieldFilterPart>,
 }
 
-#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum StepFilterType {
-    BOOLEAN,
-    TEXT,
-    DATE,
-    NUMBER,
-    STATEMENT,
-    LIST,
-    MultiList,
-    GROUP,
+string_enum! {
+    pub enum StepFilterType {
+        BOOLEAN => "BOOLEAN",
+        TEXT => "TEXT",
+        DATE => "DATE",
+        NUMBER => "NUMBER",
+        STATEMENT => "STATEMENT",
+        LIST => "LIST",
+        MultiList => "MULTI_LIST",
+        GROUP => "GROUP",
+    }
 }
 
-#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum StepFilterValue {
-    AND,
-    OR,
+string_enum! {
+    pub enum StepFilterValue {
+        AND => "AND",
+        OR => "OR",
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -95,30 +93,30 @@ pub struct FieldFilterPart {
     pub on: FieldFilterPartOn,
 }
 
-#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum FieldFilterPartOperator {
-    LARGER,
-    SMALLER,
-    LargerEqual,
-    SmallerEqual,
-    EQUAL,
-    NotEqual,
-    AllIn,
-    AnyIn,
-    NotIn,
-    BETWEEN,
-    NotBetween,
-    LIKE,
-    NotLike,
-    IN,
+string_enum! {
+    pub enum FieldFilterPartOperator {
+        LARGER => ">",
+        SMALLER => "<",
+        LargerEqual => ">=",
+        SmallerEqual => "<=",
+        EQUAL => "EQUAL",
+        NotEqual => "NOT_EQUAL",
+        AllIn => "ALL_IN",
+        AnyIn => "ANY_IN",
+        NotIn => "NOT_IN",
+        BETWEEN => "BETWEEN",
+        NotBetween => "NOT_BETWEEN",
+        LIKE => "LIKE",
+        NotLike => "NOT_LIKE",
+        IN => "IN",
+    }
 }
 
-#[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum FieldFilterPartOn {
-    SUBSCRIBER,
-    PAYLOAD,
+string_enum! {
+    pub enum FieldFilterPartOn {
+        SUBSCRIBER => "subscriber",
+        PAYLOAD => "payload",
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -154,6 +152,7 @@ pub struct ChannelTypeLimit {
     count: u32,
 }
 
+#[derive(Clone)]
 pub struct Integrations {
     client: Client,
 }
@@ -163,75 +162,30 @@ impl Integrations {
         Self { client }
     }
 
-    pub async fn get_integrations(&self) -> Result<Vec<Integration>, NovuError> {
-        let result: Response<Vec<Integration>> = self.client.get("/integrations").await?;
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
 
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                400 => {
-                    println!("{:?}", err);
-                    todo!()
-                }
-                401 => Err(NovuError::UnauthorizedError("/integrations".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+    pub async fn get_integrations(&self) -> Result<Vec<Integration>, NovuError> {
+        self.client.get("/integrations").await
     }
 
     pub async fn create(&self, data: CreateIntegrationRequest) -> Result<Integration, NovuError> {
-        let result: Response<Integration> = self.client.post("/integrations", Some(&data)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/integrations".to_string())),
-                409 => {
-                    println!("Integration already exists");
-                    todo!()
-                }
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.post("/integrations", Some(&data)).await
     }
 
     pub async fn active_integrations(&self) -> Result<Vec<Integration>, NovuError> {
-        let result: Response<Vec<Integration>> = self.client.get("/integrations/active").await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError(
-                    "/integrations/active".to_string(),
-                )),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.get("/integrations/active").await
     }
 
     pub async fn webhook_support_status(&self, provider_id: u32) -> Result<bool, NovuError> {
-        let result: Response<bool> = self
-            .client
+        self.client
             .get(format!(
                 "/integrations/webhook/provider/{}/status",
                 provider_id
             ))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError(format!(
-                    "/integrations/provider/{}/webhook-support",
-                    provider_id
-                ))),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 
     pub async fn update_integration(
@@ -239,85 +193,79 @@ impl Integrations {
         integration_id: u32,
         update_integration: UpdateIntegrationRequest,
     ) -> Result<Integration, NovuError> {
-        let result: Response<Integration> = self
-            .client
+        self.client
             .put(
                 format!("/integrations/{}", integration_id),
                 &Some(update_integration),
             )
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/integrations".to_string())),
-                409 => {
-                    println!("Integration already exists");
-                    todo!()
-                }
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 
     pub async fn delete_integration(&self, integration_id: u32) -> Result<Integration, NovuError> {
-        let result: Response<Integration> = self
-            .client
+        self.client
             .delete(format!("/integrations/{}", integration_id))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/integrations".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 
     pub async fn set_primary_integration(
         &self,
         integration_id: u32,
     ) -> Result<Integration, NovuError> {
-        let result: Response<Integration> = self
-            .client
+        self.client
             .post(
                 format!("/integrations/{}/set-primary", integration_id),
                 None::<&()>,
             )
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/integrations".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 
     pub async fn get_channel_limit(
         &self,
         channel_type: ChannelTypeEnum,
     ) -> Result<ChannelTypeLimit, NovuError> {
-        let result: Response<ChannelTypeLimit> = self
-            .client
+        self.client
             .get(format!("/integrations/{}/limit", channel_type))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError(format!(
-                    "/integrations/channel/{}/limit",
-                    channel_type
-                ))),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_filter_enums_round_trip() {
+    use crate::utils::assert_string_enum;
+
+    assert_string_enum(
+        StepFilterType::ALL,
+        &[
+            "BOOLEAN",
+            "TEXT",
+            "DATE",
+            "NUMBER",
+            "STATEMENT",
+            "LIST",
+            "MULTI_LIST",
+            "GROUP",
+        ],
+    );
+    assert_string_enum(StepFilterValue::ALL, &["AND", "OR"]);
+    assert_string_enum(
+        FieldFilterPartOperator::ALL,
+        &[
+            ">",
+            "<",
+            ">=",
+            "<=",
+            "EQUAL",
+            "NOT_EQUAL",
+            "ALL_IN",
+            "ANY_IN",
+            "NOT_IN",
+            "BETWEEN",
+            "NOT_BETWEEN",
+            "LIKE",
+            "NOT_LIKE",
+            "IN",
+        ],
+    );
+    assert_string_enum(FieldFilterPartOn::ALL, &["subscriber", "payload"]);
+}
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
 use crate::{
-    client::{Client, Response},
+    client::Client,
     error::NovuError,
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
     utils::generate_query_string,
 };
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -52,14 +55,7 @@ pub struct Layout {
     pub _parent_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LayoutResponse {
-    pub data: Vec<Layout>,
-    pub page: i32,
-    pub page_size: i32,
-    pub total_count: i32,
-}
+pub type LayoutResponse = Page<Layout>;
 
 #[derive(Clone)]
 pub struct Layouts {
@@ -71,20 +67,16 @@ impl Layouts {
         Self { client }
     }
 
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
     pub async fn create(
         &self,
         data: CreateLayoutPayload,
     ) -> Result<CreateLayoutResponse, NovuError> {
-        let result = self.client.post("/layouts", Some(&data)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/layouts".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.post("/layouts", Some(&data)).await
     }
 
     pub async fn list(
@@ -93,87 +85,55 @@ impl Layouts {
         page_size: Option<u32>,
         sort_by: Option<String>,
         order_by: Option<u32>,
-    ) -> Result<Vec<LayoutResponse>, NovuError> {
+    ) -> Result<LayoutResponse, NovuError> {
         let mut params = HashMap::new();
         params.insert("page", page.map(|p| p.to_string()));
         params.insert("pageSize", page_size.map(|l| l.to_string()));
         params.insert("sortBy", sort_by.map(|s| s.to_string()));
         params.insert("orderBy", order_by.map(|s| s.to_string()));
 
-        let result = self
-            .client
-            .get(format!("/layouts/?{}", generate_query_string(&params)))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/layouts".to_string())),
-                400 => Err(NovuError::InvalidValues(
-                    "page size".to_string(),
-                    "page size limit".to_string(),
-                )),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client
+            .get_paginated(format!("/layouts/?{}", generate_query_string(&params)))
+            .await
+    }
+
+    /// Streams every layout, fetching pages as the stream is polled.
+    pub fn list_all(
+        &self,
+        sort_by: Option<String>,
+        order_by: Option<u32>,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<Layout, NovuError>> {
+        let layouts = self.clone();
+
+        paginate(options, move |page| {
+            let layouts = layouts.clone();
+            let sort_by = sort_by.clone();
+            async move {
+                layouts
+                    .list(Some(page), options.page_size, sort_by, order_by)
+                    .await
+            }
+        })
     }
 
     pub async fn get(&self, id: String) -> Result<Layout, NovuError> {
-        let result = self.client.get(format!("/layouts/{}", id)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/layouts".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.get(format!("/layouts/{}", id)).await
     }
 
     pub async fn update(&self, id: String, data: CreateLayoutPayload) -> Result<Layout, NovuError> {
-        let result: Response<Layout> = self
-            .client
+        self.client
             .patch(format!("/layouts/{}", id), Some(&data))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/layouts".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 
     pub async fn delete(&self, id: String) -> Result<Layout, NovuError> {
-        let result = self.client.delete(format!("/layouts/{}", id)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/layouts".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.delete(format!("/layouts/{}", id)).await
     }
 
     pub async fn set_default(&self, id: String) -> Result<(), NovuError> {
-        let result: Response<()> = self
-            .client
+        self.client
             .post(format!("/layouts/{}/default", id), None::<()>.as_ref())
-            .await?;
-
-        match result {
-            Response::Success(_data) => Ok(()),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/layouts".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 }
@@ -1,62 +1,83 @@
+pub mod attachments;
 pub mod blueprint;
+pub mod builder;
 pub mod changes;
 pub mod client;
+pub mod config;
 pub mod consts;
 pub mod environments;
 pub mod error;
 pub mod events;
+pub mod execution;
 pub mod feeds;
 pub mod inbound_parse;
 pub mod integrations;
 pub mod layouts;
 pub mod messages;
+pub mod notifications;
+pub mod options;
+pub mod pagination;
+pub mod retry;
 pub mod subscriber;
+pub mod tenants;
+pub mod topics;
 pub mod utils;
+pub mod validation;
 pub mod workflows;
 
-use std::fmt::Display;
+use std::{collections::HashMap, sync::Arc};
 
+#[cfg(feature = "derive")]
+pub use novu_derive::NovuWorkflow;
+pub use workflows::NovuWorkflow;
+
+/// Paths used by the code `novu-derive` generates.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde::Serialize;
+}
+
+use blueprint::Blueprints;
+use builder::NovuBuilder;
+use changes::Changes;
 use client::Client;
+use config::NovuConfig;
+use consts::{TRIGGER_BULK_CONCURRENCY, TRIGGER_BULK_MAX_EVENTS};
 use environments::{ApiKey, Environment, EnvironmentPayload};
 use error::NovuError;
-use events::{TriggerPayload, TriggerResponse};
+use events::{
+    BroadcastPayload, BroadcastResponse, BulkTriggerError, BulkTriggerPayload, BulkTriggerResponse,
+    TriggerOverrides, TriggerPayload, TriggerResponse,
+};
+use execution::ExecutionDetails;
 use feeds::Feeds;
+use futures::{stream, StreamExt};
 use inbound_parse::InboundParse;
+use integrations::Integrations;
 use layouts::Layouts;
 use messages::Messages;
-use serde::{Deserialize, Serialize};
+use notifications::Notifications;
+use options::RequestOptions;
+use serde::Serialize;
 use subscriber::Subscribers;
+use tenants::Tenants;
+use topics::Topics;
+use utils::encode_path_segment;
+use validation::TriggerValidator;
 use workflows::Workflows;
 
-#[derive(PartialEq, Eq, PartialOrd, Serialize, Deserialize, Debug)]
-#[serde(untagged)]
-pub enum ChannelTypeEnum {
-    InApp,
-    EMAIL,
-    SMS,
-    CHAT,
-    PUSH,
-}
-
-impl Display for ChannelTypeEnum {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ChannelTypeEnum::InApp => write!(f, "in_app"),
-            ChannelTypeEnum::EMAIL => write!(f, "email"),
-            ChannelTypeEnum::SMS => write!(f, "sms"),
-            ChannelTypeEnum::CHAT => write!(f, "chat"),
-            ChannelTypeEnum::PUSH => write!(f, "push"),
-        }
+utils::string_enum! {
+    #[derive(PartialOrd)]
+    pub enum ChannelTypeEnum {
+        InApp => "in_app",
+        EMAIL => "email",
+        SMS => "sms",
+        CHAT => "chat",
+        PUSH => "push",
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct IAttachmentOptions {
-    pub mime: String,
-    pub file: Vec<u8>,
-    pub name: Option<String>,
-    pub channels: Option<Vec<ChannelTypeEnum>>,
-}
+pub type IAttachmentOptions = attachments::Attachment;
 
 #[derive(Clone)]
 pub struct Novu {
@@ -66,155 +87,276 @@ pub struct Novu {
     pub messages: Messages,
     pub workflows: Workflows,
     pub subscribers: Subscribers,
+    pub integrations: Integrations,
+    pub changes: Changes,
+    pub blueprints: Blueprints,
+    pub execution_details: ExecutionDetails,
+    pub notifications: Notifications,
+    pub topics: Topics,
+    pub tenants: Tenants,
+    trigger_validator: Option<TriggerValidator>,
 }
 
 impl Novu {
+    /// `api_url` is the base url of a self-hosted instance, without the API
+    /// version. Use [`Novu::builder`] for anything beyond that.
     pub fn new(api_key: impl ToString, api_url: Option<&str>) -> Result<Self, NovuError> {
-        let client = Client::new(api_key, api_url)?;
+        Ok(Self::from_client(Client::new(api_key, api_url)?))
+    }
+
+    pub fn builder(api_key: impl ToString) -> NovuBuilder {
+        NovuBuilder::new(api_key)
+    }
+
+    /// Reads the settings documented on [`NovuConfig`] from `NOVU_*`
+    /// environment variables.
+    pub fn from_env() -> Result<Self, NovuError> {
+        NovuConfig::from_env()?.into_builder().build()
+    }
+
+    /// Reads the settings documented on [`NovuConfig`] from a `.toml` or
+    /// `.json` file.
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, NovuError> {
+        NovuConfig::from_file(path)?.into_builder().build()
+    }
+
+    /// Builds the facade around an already configured [`Client`], e.g. one
+    /// with a [`retry::RetryPolicy`] attached.
+    pub fn from_client(client: Client) -> Self {
         let feeds = Feeds::new(client.clone_client());
         let layouts = Layouts::new(client.clone_client());
         let messages = Messages::new(client.clone_client());
         let workflows = Workflows::new(client.clone_client());
         let subscribers = Subscribers::new(client.clone_client());
+        let integrations = Integrations::new(client.clone_client());
+        let changes = Changes::new(client.clone_client());
+        let blueprints = Blueprints::new(client.clone_client());
+        let execution_details = ExecutionDetails::new(client.clone_client());
+        let notifications = Notifications::new(client.clone_client());
+        let topics = Topics::new(client.clone_client());
+        let tenants = Tenants::new(client.clone_client());
 
-        Ok(Self {
+        Self {
             client,
             feeds,
             layouts,
             messages,
             workflows,
             subscribers,
-        })
+            integrations,
+            changes,
+            blueprints,
+            execution_details,
+            notifications,
+            topics,
+            tenants,
+            trigger_validator: None,
+        }
     }
 
-    pub async fn trigger(&self, data: TriggerPayload) -> Result<TriggerResponse, NovuError> {
-        let result = self.client.post("/events/trigger", Some(&data)).await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => Err(NovuError::UnexpectedResponse {
-                msg: err.message,
-                code: err.status_code.to_string(),
-            }),
-            crate::client::Response::Messages(err) => Err(NovuError::UnexpectedResponse {
-                msg: format!("{:?}", err.message),
-                code: err.status_code.to_string(),
-            }),
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self {
+            trigger_validator: self.trigger_validator.clone(),
+            ..Self::from_client(self.client.clone_client().with_options(options))
         }
     }
 
-    pub async fn current_environment(&self) -> Result<Environment, NovuError> {
-        let result = self.client.get("/environments/me").await?;
-        match result {
-            client::Response::Success(data) => Ok(data.data),
-            client::Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/environments/me".to_string())),
-                code => todo!("{}", code),
-            },
-            client::Response::Messages(err) => Err(NovuError::InvalidValues(
-                "current_environment".to_string(),
-                format!("{:?}", err.message),
-            )),
+    /// Returns a copy that checks payloads against the workflow's variables
+    /// before [`Novu::trigger`], [`Novu::trigger_bulk`] and
+    /// [`Novu::broadcast`] send them. See [`TriggerValidator`].
+    pub fn with_trigger_validation(&self) -> Self {
+        self.with_trigger_validator(TriggerValidator::new(self.workflows.clone()))
+    }
+
+    /// Like [`Novu::with_trigger_validation`], with a configured validator.
+    pub fn with_trigger_validator(&self, validator: TriggerValidator) -> Self {
+        Self {
+            trigger_validator: Some(validator),
+            ..self.clone()
         }
     }
 
-    pub async fn get_environments(&self) -> Result<Vec<Environment>, NovuError> {
-        let result = self.client.get("/environments").await?;
-        match result {
-            client::Response::Success(data) => Ok(data.data),
-            client::Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/environments".to_string())),
-                code => todo!("{}", code),
-            },
-            client::Response::Messages(err) => Err(NovuError::InvalidValues(
-                "current_environment".to_string(),
-                format!("{:?}", err.message),
-            )),
+    pub async fn trigger<T: Serialize>(
+        &self,
+        data: TriggerPayload<T>,
+    ) -> Result<TriggerResponse, NovuError> {
+        if let Some(validator) = &self.trigger_validator {
+            validator.validate(&data).await?;
+        }
+
+        self.client.post("/events/trigger", Some(&data)).await
+    }
+
+    /// Cancels the pending delayed or digested steps of a triggered event.
+    /// Returns whether anything was cancelled; an unknown transaction id is
+    /// reported as [`NovuError::NotFound`].
+    pub async fn cancel_trigger(&self, transaction_id: impl AsRef<str>) -> Result<bool, NovuError> {
+        self.client
+            .delete(format!(
+                "/events/trigger/{}",
+                encode_path_segment(transaction_id.as_ref())
+            ))
+            .await
+    }
+
+    /// Triggers the `name` workflow for every subscriber in the environment.
+    pub async fn broadcast(
+        &self,
+        name: impl ToString,
+        payload: impl Serialize,
+        overrides: Option<TriggerOverrides>,
+    ) -> Result<BroadcastResponse, NovuError> {
+        let data = BroadcastPayload {
+            name: name.to_string(),
+            payload,
+            overrides,
+        };
+
+        if let Some(validator) = &self.trigger_validator {
+            validator
+                .validate_payload(&data.name, &data.payload)
+                .await?;
+        }
+
+        self.client
+            .post("/events/trigger/broadcast", Some(&data))
+            .await
+    }
+
+    /// Triggers many events through `/events/trigger/bulk`, split into
+    /// batches of at most [`TRIGGER_BULK_MAX_EVENTS`] with up to
+    /// [`TRIGGER_BULK_CONCURRENCY`] batches in flight. Failures are reported
+    /// per event rather than failing the whole call.
+    ///
+    /// An explicit idempotency key is sent as `{key}-{batch}` when the events
+    /// span more than one batch. With trigger validation enabled, invalid
+    /// events are reported as [`BulkTriggerError::Invalid`] and not sent.
+    pub async fn trigger_bulk<T: Serialize>(
+        &self,
+        events: Vec<TriggerPayload<T>>,
+    ) -> BulkTriggerResponse {
+        let validator = match &self.trigger_validator {
+            Some(validator) => validator,
+            None => {
+                return BulkTriggerResponse {
+                    results: self.send_bulk(events).await,
+                }
+            }
+        };
+
+        let len = events.len();
+        let mut invalid = HashMap::new();
+        let mut valid = Vec::new();
+        for (index, event) in events.into_iter().enumerate() {
+            match validator.validate(&event).await {
+                Ok(()) => valid.push(event),
+                Err(err) => {
+                    invalid.insert(index, err);
+                }
+            }
+        }
+
+        let mut sent = self.send_bulk(valid).await.into_iter();
+        let results = (0..len)
+            .map(|index| match invalid.remove(&index) {
+                Some(err) => Err(BulkTriggerError::Invalid(err)),
+                None => sent.next().unwrap_or_else(|| {
+                    Err(BulkTriggerError::Request(Arc::new(
+                        NovuError::UnexpectedResponse {
+                            msg: format!("no bulk result for event {}", index),
+                            code: "200".to_string(),
+                        },
+                    )))
+                }),
+            })
+            .collect();
+
+        BulkTriggerResponse { results }
+    }
+
+    /// Sends `events` in batches, returning one result per event.
+    async fn send_bulk<T: Serialize>(
+        &self,
+        events: Vec<TriggerPayload<T>>,
+    ) -> Vec<Result<TriggerResponse, BulkTriggerError>> {
+        let mut batches = Vec::new();
+        let mut events = events.into_iter().peekable();
+        while events.peek().is_some() {
+            batches.push(BulkTriggerPayload {
+                events: events.by_ref().take(TRIGGER_BULK_MAX_EVENTS).collect(),
+            });
         }
+
+        let clients = self.client.split_idempotency_key(batches.len());
+        let outcomes: Vec<_> = stream::iter(batches.into_iter().zip(clients))
+            .map(|(batch, client)| async move {
+                let len = batch.events.len();
+                let result: Result<Vec<TriggerResponse>, NovuError> =
+                    client.post("/events/trigger/bulk", Some(&batch)).await;
+                (len, result)
+            })
+            .buffered(TRIGGER_BULK_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut results = Vec::new();
+        for (len, outcome) in outcomes {
+            match outcome {
+                Ok(responses) if responses.len() == len => {
+                    results.extend(responses.into_iter().map(
+                        |response| match response.is_rejected() {
+                            true => Err(BulkTriggerError::Rejected(response)),
+                            false => Ok(response),
+                        },
+                    ));
+                }
+                Ok(responses) => {
+                    let err = Arc::new(NovuError::UnexpectedResponse {
+                        msg: format!("expected {} bulk results, got {}", len, responses.len()),
+                        code: "200".to_string(),
+                    });
+                    results.extend((0..len).map(|_| Err(BulkTriggerError::Request(err.clone()))));
+                }
+                Err(err) => {
+                    let err = Arc::new(err);
+                    results.extend((0..len).map(|_| Err(BulkTriggerError::Request(err.clone()))));
+                }
+            }
+        }
+
+        results
+    }
+
+    pub async fn current_environment(&self) -> Result<Environment, NovuError> {
+        self.client.get("/environments/me").await
+    }
+
+    pub async fn get_environments(&self) -> Result<Vec<Environment>, NovuError> {
+        self.client.get("/environments").await
     }
 
     pub async fn create_environment(
         &self,
         data: EnvironmentPayload,
     ) -> Result<Environment, NovuError> {
-        let result = self.client.post("/environments", Some(&data)).await?;
-        match result {
-            client::Response::Success(data) => Ok(data.data),
-            client::Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/environments".to_string())),
-                code => todo!("{}", code),
-            },
-
-            client::Response::Messages(err) => Err(NovuError::InvalidValues(
-                "current_environment".to_string(),
-                format!("{:?}", err.message),
-            )),
-        }
+        self.client.post("/environments", Some(&data)).await
     }
 
     pub async fn get_environment_api_keys(&self) -> Result<ApiKey, NovuError> {
-        let result = self.client.get("/environments/api-keys").await?;
-        match result {
-            client::Response::Success(data) => Ok(data.data),
-            client::Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError(
-                    "/environments/api-keys".to_string(),
-                )),
-                code => todo!("{}", code),
-            },
-            client::Response::Messages(err) => Err(NovuError::InvalidValues(
-                "current_environment".to_string(),
-                format!("{:?}", err.message),
-            )),
-        }
+        self.client.get("/environments/api-keys").await
     }
 
     pub async fn regenerate_environment_api_keys(&self) -> Result<ApiKey, NovuError> {
-        let result = self
-            .client
+        self.client
             .post("/environments/api-keys/regenerate", None::<&()>)
-            .await?;
-        match result {
-            client::Response::Success(data) => Ok(data.data),
-            client::Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError(
-                    "/environments/api-keys".to_string(),
-                )),
-                400 => {
-                    println!("{:?}", err);
-                    todo!()
-                }
-                code => todo!("{}", code),
-            },
-            client::Response::Messages(err) => Err(NovuError::InvalidValues(
-                "current_environment".to_string(),
-                format!("{:?}", err.message),
-            )),
-        }
+            .await
     }
 
     pub async fn validate_mx_record_setup_for_inbound_parse(
         &self,
     ) -> Result<InboundParse, NovuError> {
-        let result = self.client.get("/inbound-parse/mx/status").await?;
-
-        match result {
-            client::Response::Success(data) => Ok(data.data),
-            client::Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError(
-                    "/inbound-parse/mx/status".to_string(),
-                )),
-                400 => {
-                    println!("{:?}", err);
-                    todo!()
-                }
-                code => todo!("{}", code),
-            },
-            client::Response::Messages(err) => Err(NovuError::InvalidValues(
-                "validating".to_string(),
-                format!("{:?}", err.message),
-            )),
-        }
+        self.client.get("/inbound-parse/mx/status").await
     }
 }
 
@@ -223,16 +365,18 @@ impl Novu {
 async fn test_trigger() {
     let novu = Novu::new("", None).unwrap();
     let result = novu
-        .trigger(TriggerPayload {
-            name: "testing".to_string(),
-            payload: std::collections::HashMap::new(),
-            to: events::TriggerRecipientsType::Single(
-                events::TriggerRecipientBuilder::new("test_subscriber_id")
-                    .first_name("Test")
-                    .last_name("testing")
-                    .build(),
-            ),
-        })
+        .trigger(
+            TriggerPayload::builder(
+                "testing",
+                events::TriggerRecipientsType::Single(
+                    events::TriggerRecipientBuilder::new("test_subscriber_id")
+                        .first_name("Test")
+                        .last_name("testing")
+                        .build(),
+                ),
+            )
+            .build(),
+        )
         .await;
 
     assert!(result.is_err());
@@ -279,3 +423,487 @@ async fn test_regenerate_environment_api_keys() {
     let regenerate_api_keys_result = novu.regenerate_environment_api_keys().await;
     assert!(regenerate_api_keys_result.is_err());
 }
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_trigger_with_idempotency_key() {
+    use wiremock::{
+        matchers::{header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .and(header("Idempotency-Key", "statement-2023-10"))
+        .respond_with(ResponseTemplate::new(201).set_body_string(
+            r#"{"data":{"acknowledged":true,"status":"processed","transactionId":"txn"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = Novu::new("", Some(&server.uri())).unwrap();
+    let result = novu
+        .with_options(
+            RequestOptions::builder()
+                .idempotency_key("statement-2023-10")
+                .build(),
+        )
+        .trigger(
+            TriggerPayload::builder(
+                "statement",
+                events::TriggerRecipientsType::Single(
+                    events::TriggerRecipientBuilder::new("test_subscriber_id").build(),
+                ),
+            )
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.transaction_id, "txn");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_trigger_bulk_reports_failures_by_index() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger/bulk"))
+        .respond_with(|request: &Request| {
+            let body: serde_json::Value = request.body_json().unwrap();
+            let events = body["events"].as_array().unwrap();
+            assert!(events.len() <= TRIGGER_BULK_MAX_EVENTS);
+
+            if events.iter().any(|event| event["name"] == "outage") {
+                return ResponseTemplate::new(500);
+            }
+
+            let data: Vec<_> = events
+                .iter()
+                .map(|event| match event["name"] == "inactive" {
+                    true => serde_json::json!({
+                        "acknowledged": true,
+                        "status": "error",
+                        "error": ["workflow is not active"],
+                    }),
+                    false => serde_json::json!({
+                        "acknowledged": true,
+                        "status": "processed",
+                        "transactionId": "txn",
+                    }),
+                })
+                .collect();
+            ResponseTemplate::new(201).set_body_json(serde_json::json!({ "data": data }))
+        })
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let event = |name: &str| {
+        TriggerPayload::builder(
+            name,
+            events::TriggerRecipientsType::Single(
+                events::TriggerRecipientBuilder::new("test_subscriber_id").build(),
+            ),
+        )
+        .build()
+    };
+    let mut payloads: Vec<_> = (0..250).map(|_| event("statement")).collect();
+    payloads[7] = event("inactive");
+    payloads[230] = event("outage");
+
+    let novu = Novu::new("", Some(&server.uri())).unwrap();
+    let response = novu.trigger_bulk(payloads).await;
+
+    assert_eq!(response.results.len(), 250);
+    assert!(!response.is_success());
+
+    let failed: Vec<usize> = response.failures().map(|(index, _)| index).collect();
+    assert_eq!(failed[0], 7);
+    assert_eq!(failed[1..], (200..250).collect::<Vec<_>>()[..]);
+    assert!(matches!(
+        response.results[7],
+        Err(BulkTriggerError::Rejected(_))
+    ));
+    assert!(matches!(
+        response.results[249],
+        Err(BulkTriggerError::Request(ref err)) if matches!(**err, NovuError::ServerError { .. })
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_trigger_bulk_splits_idempotency_key() {
+    use std::sync::{Arc, Mutex};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let seen = keys.clone();
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger/bulk"))
+        .respond_with(move |request: &Request| {
+            let (_, key) = request
+                .headers
+                .iter()
+                .find(|(name, _)| {
+                    name.as_str()
+                        .eq_ignore_ascii_case(consts::IDEMPOTENCY_KEY_HEADER)
+                })
+                .unwrap();
+            seen.lock().unwrap().push(key.last().as_str().to_string());
+
+            let body: serde_json::Value = request.body_json().unwrap();
+            let data: Vec<_> = body["events"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|_| serde_json::json!({ "acknowledged": true, "status": "processed" }))
+                .collect();
+            ResponseTemplate::new(201).set_body_json(serde_json::json!({ "data": data }))
+        })
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let payloads: Vec<_> = (0..250)
+        .map(|_| {
+            TriggerPayload::builder(
+                "statement",
+                events::TriggerRecipientsType::SubscriberId("sub-1".to_string()),
+            )
+            .build()
+        })
+        .collect();
+
+    let novu = Novu::new("", Some(&server.uri()))
+        .unwrap()
+        .with_options(RequestOptions::builder().idempotency_key("run-7").build());
+    let response = novu.trigger_bulk(payloads).await;
+    assert!(response.is_success());
+
+    let mut keys = keys.lock().unwrap().clone();
+    keys.sort();
+    assert_eq!(keys, ["run-7-0", "run-7-1", "run-7-2"]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_bulk_and_broadcast_are_validated() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/workflows/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 100,
+            "totalCount": 1,
+            "data": [{
+                "name": "statement",
+                "description": "",
+                "active": true,
+                "preferenceSettings": {},
+                "critical": false,
+                "tags": [],
+                "steps": [],
+                "triggers": [{
+                    "type": "event",
+                    "identifier": "statement",
+                    "variables": [{ "name": "amount", "required": true }],
+                    "subscriberVariables": [],
+                }],
+            }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger/bulk"))
+        .respond_with(|request: &Request| {
+            let body: serde_json::Value = request.body_json().unwrap();
+            assert_eq!(body["events"].as_array().unwrap().len(), 2);
+            ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": [
+                    { "acknowledged": true, "status": "processed" },
+                    { "acknowledged": true, "status": "processed" },
+                ],
+            }))
+        })
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let event = |payload: serde_json::Value| {
+        TriggerPayload::builder(
+            "statement",
+            events::TriggerRecipientsType::SubscriberId("sub-1".to_string()),
+        )
+        .payload(payload)
+        .build()
+    };
+
+    let novu = Novu::new("", Some(&server.uri()))
+        .unwrap()
+        .with_trigger_validation();
+    let response = novu
+        .trigger_bulk(vec![
+            event(serde_json::json!({ "amount": 1 })),
+            event(serde_json::json!({})),
+            event(serde_json::json!({ "amount": 3 })),
+        ])
+        .await;
+
+    assert!(response.results[0].is_ok());
+    assert!(matches!(
+        response.results[1],
+        Err(BulkTriggerError::Invalid(NovuError::InvalidPayload { .. }))
+    ));
+    assert!(response.results[2].is_ok());
+
+    let broadcast = novu
+        .broadcast("statement", serde_json::json!({}), None)
+        .await;
+    assert!(matches!(broadcast, Err(NovuError::InvalidPayload { .. })));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_validated_bulk_with_missing_results() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/workflows/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 100,
+            "totalCount": 1,
+            "data": [{
+                "triggers": [{
+                    "type": "event",
+                    "identifier": "statement",
+                    "variables": [{ "name": "amount" }],
+                    "subscriberVariables": [],
+                }],
+            }],
+        })))
+        .mount(&server)
+        .await;
+    // One result for a batch of two events.
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger/bulk"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "data": [{ "acknowledged": true, "status": "processed" }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let event = |payload: serde_json::Value| {
+        TriggerPayload::builder(
+            "statement",
+            events::TriggerRecipientsType::SubscriberId("sub-1".to_string()),
+        )
+        .payload(payload)
+        .build()
+    };
+
+    let novu = Novu::new("", Some(&server.uri()))
+        .unwrap()
+        .with_trigger_validation();
+    let response = novu
+        .trigger_bulk(vec![
+            event(serde_json::json!({ "amount": 1 })),
+            event(serde_json::json!({})),
+            event(serde_json::json!({ "amount": 3 })),
+        ])
+        .await;
+
+    assert_eq!(response.results.len(), 3);
+    assert!(matches!(
+        response.results[0],
+        Err(BulkTriggerError::Request(_))
+    ));
+    assert!(matches!(
+        response.results[1],
+        Err(BulkTriggerError::Invalid(_))
+    ));
+    assert!(matches!(
+        response.results[2],
+        Err(BulkTriggerError::Request(_))
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_broadcast() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger/broadcast"))
+        .and(body_json(serde_json::json!({
+            "name": "maintenance",
+            "payload": { "window": "02:00-03:00" },
+            "overrides": { "email": { "from": "ops@example.com" } },
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_string(
+            r#"{"data":{"acknowledged":true,"status":"processed","transactionId":"txn"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut payload = std::collections::HashMap::new();
+    payload.insert(
+        "window".to_string(),
+        events::AllowedPayloadValues::STRING("02:00-03:00".to_string()),
+    );
+    let overrides = TriggerOverrides {
+        email: Some(events::EmailOverrides {
+            from: Some("ops@example.com".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let novu = Novu::new("", Some(&server.uri())).unwrap();
+    let response = novu
+        .broadcast("maintenance", payload, Some(overrides))
+        .await
+        .unwrap();
+
+    assert!(response.acknowledged);
+    assert_eq!(response.transaction_id, "txn");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_cancel_trigger() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/events/trigger/order%2F42%20pending"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"data":true}"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/events/trigger/txn-unknown"))
+        .respond_with(ResponseTemplate::new(404).set_body_string(
+            r#"{"statusCode":404,"message":"Transaction not found","error":"Not Found"}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let novu = Novu::new("", Some(&server.uri())).unwrap();
+    assert!(novu.cancel_trigger("order/42 pending").await.unwrap());
+    assert!(matches!(
+        novu.cancel_trigger("txn-unknown").await,
+        Err(NovuError::NotFound { .. })
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_trigger_with_typed_payload() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Serialize)]
+    struct Payout {
+        amount_sats: i64,
+        fee_rate: f64,
+        memo: Option<String>,
+        wallet: Wallet,
+    }
+
+    #[derive(Serialize)]
+    struct Wallet {
+        label: String,
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .and(body_json(serde_json::json!({
+            "name": "payout-sent",
+            "to": "sub-1",
+            "payload": {
+                "amount_sats": 2_100_000_000_000_000_i64,
+                "fee_rate": 1.5,
+                "memo": null,
+                "wallet": { "label": "cold storage" },
+            },
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_string(
+            r#"{"data":{"acknowledged":true,"status":"processed","transactionId":"txn"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = Novu::new("", Some(&server.uri())).unwrap();
+    let result = novu
+        .trigger(
+            TriggerPayload::builder(
+                "payout-sent",
+                events::TriggerRecipientsType::builder()
+                    .subscriber_id("sub-1")
+                    .build()
+                    .unwrap(),
+            )
+            .payload(Payout {
+                amount_sats: 2_100_000_000_000_000,
+                fee_rate: 1.5,
+                memo: None,
+                wallet: Wallet {
+                    label: "cold storage".to_string(),
+                },
+            })
+            .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(result.transaction_id, "txn");
+}
+
+#[cfg(test)]
+#[test]
+fn test_channel_type_round_trip() {
+    utils::assert_string_enum(
+        ChannelTypeEnum::ALL,
+        &["in_app", "email", "sms", "chat", "push"],
+    );
+    assert_eq!(
+        ChannelTypeEnum::from("whatsapp"),
+        ChannelTypeEnum::Unknown("whatsapp".to_string())
+    );
+}
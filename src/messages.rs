@@ -1,129 +1,141 @@
 use crate::{
-    client::{Client, Response},
+    client::Client,
     error::NovuError,
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::string_enum,
     ChannelTypeEnum,
 };
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DeleteMessagePayload {
-    pub _id: String,
-}
+// The activity feed types used to be defined here; they live with the
+// services that return them now and are re-exported under their old names.
+pub use crate::{
+    execution::ExecutionDetail as Execution,
+    notifications::{Job, JobStep as Step, Subscriber, Template},
+    workflows::{Child, Filter, Trigger, TriggerVariable as Name},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Subscriber {
-    pub first_name: String,
-    pub last_name: String,
-    pub email: String,
-    pub phone: String,
+pub struct DeleteMessagePayload {
     pub _id: String,
 }
 
+/// A message sent to a subscriber through one channel.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Name {
-    pub name: String,
-}
-
+pub struct Message {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_environmentId")]
+    pub environment_id: Option<String>,
+    #[serde(rename = "_organizationId")]
+    pub organization_id: Option<String>,
+    #[serde(rename = "_notificationId")]
+    pub notification_id: Option<String>,
+    #[serde(rename = "_subscriberId")]
+    pub subscriber_id: Option<String>,
+    #[serde(rename = "_templateId")]
+    pub template_id: Option<String>,
+    #[serde(rename = "_messageTemplateId")]
+    pub message_template_id: Option<String>,
+    #[serde(rename = "_feedId")]
+    pub feed_id: Option<String>,
+    pub template_identifier: Option<String>,
+    pub transaction_id: Option<String>,
+    pub channel: Option<ChannelTypeEnum>,
+    pub provider_id: Option<String>,
+    pub subject: Option<String>,
+    /// Plain text, or the editor blocks of in-app messages.
+    pub content: Option<Value>,
+    pub cta: Option<MessageCta>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub direct_webhook_url: Option<String>,
+    pub device_tokens: Option<Vec<String>>,
+    #[serde(default)]
+    pub seen: bool,
+    #[serde(default)]
+    pub read: bool,
+    pub status: Option<String>,
+    pub error_id: Option<String>,
+    pub error_text: Option<Value>,
+    pub payload: Option<Value>,
+    pub created_at: Option<String>,
+    pub last_seen_date: Option<String>,
+    pub last_read_date: Option<String>,
+}
+
+/// What happens when an in-app message, or one of its buttons, is clicked.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Trigger {
+pub struct MessageCta {
     #[serde(rename = "type")]
-    pub type_id: String,
-    pub identifier: String,
-    pub variables: Vec<Name>,
-    pub subscriber_variables: Vec<Name>,
+    pub cta_type: Option<String>,
+    pub data: Option<MessageCtaData>,
+    pub action: Option<MessageAction>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Template {
-    pub _id: String,
-    pub name: String,
-    pub triggers: Vec<Trigger>,
+pub struct MessageCtaData {
+    pub url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Execution {
-    pub _id: String,
-    pub _job_id: String,
-    pub status: String,
-    pub detail: String,
-    pub is_retry: bool,
-    pub is_test: bool,
-    pub provider_id: Option<Value>,
-    pub raw: String,
-    pub source: String,
+pub struct MessageAction {
+    pub status: Option<MessageActionStatus>,
+    #[serde(default)]
+    pub buttons: Vec<MessageActionButton>,
+    pub result: Option<MessageActionResult>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Child {
-    pub field: String,
-    pub value: String,
-    pub operator: String,
-    pub on: String,
+pub struct MessageActionButton {
+    #[serde(rename = "type")]
+    pub button_type: MessageActionType,
+    pub content: String,
+    pub result_content: Option<String>,
 }
 
+/// The button the subscriber clicked, and the payload sent along with it.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Filter {
-    pub is_negated: bool,
+pub struct MessageActionResult {
     #[serde(rename = "type")]
-    pub filter_id: String,
-    pub value: String,
-    pub children: Vec<Child>,
+    pub action_type: Option<MessageActionType>,
+    pub payload: Option<Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Step {
-    pub _id: String,
-    pub active: bool,
-    pub filter: Filter,
-    pub template: Option<Value>,
+string_enum! {
+    pub enum MessageActionType {
+        Primary => "primary",
+        Secondary => "secondary",
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Job {
-    _id: String,
-    #[serde(rename = "type")]
-    job_id: String,
-    digest: Option<Value>,
-    execution_details: Vec<Execution>,
-    step: Step,
-    payload: Option<Value>,
-    provider_id: Option<Value>,
-    status: String,
+string_enum! {
+    pub enum MessageActionStatus {
+        Pending => "pending",
+        Done => "done",
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Message {
-    pub _id: String,
-    pub _environment_id: String,
-    pub _organization_id: String,
-    pub transaction_id: String,
-    pub created_at: String,
-    pub channels: String,
-    pub subscriber: Subscriber,
-    pub template: Template,
-    pub jobs: Vec<Job>,
+string_enum! {
+    pub enum MarkMessagesAs {
+        Read => "read",
+        Seen => "seen",
+        Unread => "unread",
+        Unseen => "unseen",
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MessageResponse {
-    pub page: i32,
-    pub total_count: i32,
-    pub has_more: bool,
-    pub data: Vec<Message>,
-}
+pub type MessageResponse = Page<Message>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -163,6 +175,11 @@ impl Messages {
         Self { client }
     }
 
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
     pub async fn get(
         &self,
         channel: String,
@@ -174,35 +191,41 @@ impl Messages {
         let query_string =
             generate_query_string(&channel, &subscriber_id, &transaction_id, page, limit);
 
-        let result: Response<MessageResponse> = self
-            .client
-            .get(format!("/messages/?{}", query_string))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/messages".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client
+            .get_paginated(format!("/messages/?{}", query_string))
+            .await
+    }
+
+    /// Streams every matching message, fetching pages as the stream is
+    /// polled. Novu's default of 10 messages per page is used unless
+    /// `options` sets a page size.
+    pub fn list_all(
+        &self,
+        channel: String,
+        subscriber_id: String,
+        transaction_id: Vec<String>,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<Message, NovuError>> {
+        let messages = self.clone();
+        let limit = options.page_size.unwrap_or(10) as i32;
+
+        paginate(options, move |page| {
+            let messages = messages.clone();
+            let channel = channel.clone();
+            let subscriber_id = subscriber_id.clone();
+            let transaction_id = transaction_id.clone();
+            async move {
+                messages
+                    .get(channel, subscriber_id, transaction_id, page as i32, limit)
+                    .await
+            }
+        })
     }
 
     pub async fn delete(&self, payload: DeleteMessagePayload) -> Result<(), NovuError> {
-        let result: Response<()> = self
-            .client
+        self.client
             .delete(&format!("/messages/{}", payload._id))
-            .await?;
-
-        match result {
-            Response::Success(_) => Ok(()),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/messages".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 
     pub async fn delete_message_by_transaction_id(
@@ -213,18 +236,17 @@ impl Messages {
 
         if let Some(channel) = &payload.channel {
             // Add the "channel" query parameter if it's Some.
-            url.push_str(&format!("?channel={:?}", channel));
+            url.push_str(&format!("?channel={}", channel));
         }
 
-        let result: Response<()> = self.client.delete(&url).await?;
-
-        match result {
-            Response::Success(_) => Ok(()),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/messages".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.delete(&url).await
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_message_enums_round_trip() {
+    crate::utils::assert_string_enum(MessageActionType::ALL, &["primary", "secondary"]);
+    crate::utils::assert_string_enum(MessageActionStatus::ALL, &["pending", "done"]);
+    crate::utils::assert_string_enum(MarkMessagesAs::ALL, &["read", "seen", "unread", "unseen"]);
+}
@@ -0,0 +1,400 @@
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    client::Client,
+    error::NovuError,
+    execution::{ExecutionDetail, StepType},
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::string_enum,
+    workflows::Trigger,
+    ChannelTypeEnum,
+};
+
+string_enum! {
+    pub enum JobStatus {
+        Pending => "pending",
+        Queued => "queued",
+        Running => "running",
+        Completed => "completed",
+        Failed => "failed",
+        Delayed => "delayed",
+        Canceled => "canceled",
+        Merged => "merged",
+        Skipped => "skipped",
+    }
+}
+
+/// One triggered workflow run as shown in the activity feed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_environmentId")]
+    pub environment_id: String,
+    #[serde(rename = "_organizationId")]
+    pub organization_id: String,
+    pub transaction_id: String,
+    pub created_at: Option<String>,
+    pub channels: Option<Vec<StepType>>,
+    pub subscriber: Option<Subscriber>,
+    pub template: Option<Template>,
+    #[serde(default)]
+    pub jobs: Vec<Job>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscriber {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub subscriber_id: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+/// The workflow a notification was triggered for.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    #[serde(rename = "_id")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub triggers: Vec<Trigger>,
+}
+
+/// One step of a notification, e.g. sending the email.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "type")]
+    pub job_type: StepType,
+    pub status: JobStatus,
+    pub provider_id: Option<String>,
+    pub digest: Option<Value>,
+    pub payload: Option<Value>,
+    pub step: Option<JobStep>,
+    #[serde(default)]
+    pub execution_details: Vec<ExecutionDetail>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStep {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub active: bool,
+    pub filters: Option<Vec<Value>>,
+    pub template: Option<Value>,
+}
+
+pub type NotificationsResponse = Page<Notification>;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationStats {
+    pub weekly_sent: u64,
+    pub monthly_sent: u64,
+}
+
+/// The notifications sent on one day.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationGraphStats {
+    /// The day, as `YYYY-MM-DD`.
+    #[serde(rename = "_id")]
+    pub date: String,
+    pub count: u64,
+    #[serde(default)]
+    pub templates: Vec<String>,
+    #[serde(default)]
+    pub channels: Vec<ChannelTypeEnum>,
+}
+
+/// Narrows down [`Notifications::list`]. Empty filters match everything.
+#[derive(Debug, Clone, Default)]
+pub struct NotificationsFilter {
+    pub channels: Vec<ChannelTypeEnum>,
+    /// Workflow ids.
+    pub templates: Vec<String>,
+    pub emails: Vec<String>,
+    pub subscriber_ids: Vec<String>,
+    pub search: Option<String>,
+    pub transaction_id: Option<String>,
+}
+
+impl NotificationsFilter {
+    fn query_string(&self, page: u32) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+
+        for channel in &self.channels {
+            query.append_pair("channels[]", channel.as_str());
+        }
+        for template in &self.templates {
+            query.append_pair("templates[]", template);
+        }
+        for email in &self.emails {
+            query.append_pair("emails[]", email);
+        }
+        for subscriber_id in &self.subscriber_ids {
+            query.append_pair("subscriberIds[]", subscriber_id);
+        }
+        if let Some(search) = &self.search {
+            query.append_pair("search", search);
+        }
+        if let Some(transaction_id) = &self.transaction_id {
+            query.append_pair("transactionId", transaction_id);
+        }
+        query.append_pair("page", &page.to_string());
+
+        query.finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct Notifications {
+    client: Client,
+}
+
+impl Notifications {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
+    pub async fn list(
+        &self,
+        filter: &NotificationsFilter,
+        page: u32,
+    ) -> Result<NotificationsResponse, NovuError> {
+        self.client
+            .get_paginated(format!("/notifications?{}", filter.query_string(page)))
+            .await
+    }
+
+    /// Streams every matching notification, fetching pages as the stream is
+    /// polled. Novu picks the page size for this endpoint.
+    pub fn list_all(
+        &self,
+        filter: NotificationsFilter,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<Notification, NovuError>> {
+        let notifications = self.clone();
+
+        paginate(options, move |page| {
+            let notifications = notifications.clone();
+            let filter = filter.clone();
+            async move { notifications.list(&filter, page).await }
+        })
+    }
+
+    pub async fn get(&self, notification_id: impl AsRef<str>) -> Result<Notification, NovuError> {
+        self.client
+            .get(format!("/notifications/{}", notification_id.as_ref()))
+            .await
+    }
+
+    pub async fn stats(&self) -> Result<NotificationStats, NovuError> {
+        self.client.get("/notifications/stats").await
+    }
+
+    /// Per-day counts for the last `days` days, or Novu's default range.
+    pub async fn graph_stats(
+        &self,
+        days: Option<u32>,
+    ) -> Result<Vec<NotificationGraphStats>, NovuError> {
+        match days {
+            Some(days) => {
+                self.client
+                    .get(format!("/notifications/graph/stats?days={}", days))
+                    .await
+            }
+            None => self.client.get("/notifications/graph/stats").await,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_filter_query_string() {
+    let filter = NotificationsFilter {
+        channels: vec![ChannelTypeEnum::EMAIL, ChannelTypeEnum::SMS],
+        emails: vec!["ada+billing@example.com".to_string()],
+        search: Some("invoice paid".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        filter.query_string(2),
+        "channels%5B%5D=email&channels%5B%5D=sms&emails%5B%5D=ada%2Bbilling%40example.com\
+         &search=invoice+paid&page=2"
+    );
+    assert_eq!(NotificationsFilter::default().query_string(0), "page=0");
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_list_and_get_notifications() {
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let notification = serde_json::json!({
+        "_id": "notification-1",
+        "_environmentId": "env-1",
+        "_organizationId": "org-1",
+        "transactionId": "txn",
+        "createdAt": "2023-10-01T12:00:00.000Z",
+        "channels": ["email"],
+        "subscriber": { "_id": "s-1", "subscriberId": "sub-1", "email": "ada@example.com" },
+        "template": {
+            "_id": "workflow-1",
+            "name": "Invoice paid",
+            "triggers": [{
+                "type": "event",
+                "identifier": "invoice-paid",
+                "variables": [{ "name": "amount" }],
+                "subscriberVariables": [],
+            }],
+        },
+        "jobs": [{
+            "_id": "job-1",
+            "type": "email",
+            "status": "failed",
+            "providerId": "sendgrid",
+            "step": { "_id": "step-1", "active": true, "filters": [] },
+            "executionDetails": [{
+                "_id": "detail-1",
+                "_jobId": "job-1",
+                "_notificationId": "notification-1",
+                "_subscriberId": "s-1",
+                "transactionId": "txn",
+                "channel": "email",
+                "status": "Failed",
+                "detail": "Unexpected provider error",
+                "source": "Internal",
+                "providerId": "sendgrid",
+                "raw": "{}",
+                "isRetry": false,
+                "isTest": false,
+                "createdAt": "2023-10-01T12:00:01.000Z",
+            }],
+        }],
+    });
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notifications"))
+        .and(query_param("transactionId", "txn"))
+        .and(query_param("page", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 10,
+            "hasMore": false,
+            "data": [notification.clone()],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notifications/notification-1"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": notification })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let filter = NotificationsFilter {
+        transaction_id: Some("txn".to_string()),
+        ..Default::default()
+    };
+    let page = novu.notifications.list(&filter, 0).await.unwrap();
+    assert_eq!(page.has_more, Some(false));
+    assert_eq!(page.data[0].jobs[0].job_type, StepType::Email);
+    assert_eq!(page.data[0].jobs[0].status, JobStatus::Failed);
+
+    let notification = novu.notifications.get("notification-1").await.unwrap();
+    let execution = &notification.jobs[0].execution_details[0];
+    assert_eq!(
+        execution.status,
+        crate::execution::ExecutionDetailStatus::Failed
+    );
+    assert_eq!(
+        notification.template.unwrap().triggers[0].identifier,
+        "invoice-paid"
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_notification_stats() {
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notifications/stats"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"data":{"weeklySent":12,"monthlySent":40}}"#),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/notifications/graph/stats"))
+        .and(query_param("days", "7"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            r#"{"data":[{"_id":"2023-10-01","count":3,"templates":["w-1"],"channels":["email","sms"]}]}"#,
+        ))
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let stats = novu.notifications.stats().await.unwrap();
+    assert_eq!((stats.weekly_sent, stats.monthly_sent), (12, 40));
+
+    let graph = novu.notifications.graph_stats(Some(7)).await.unwrap();
+    assert_eq!(graph[0].date, "2023-10-01");
+    assert_eq!(
+        graph[0].channels,
+        [ChannelTypeEnum::EMAIL, ChannelTypeEnum::SMS]
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_job_status() {
+    use crate::utils::assert_string_enum;
+
+    assert_string_enum(
+        JobStatus::ALL,
+        &[
+            "pending",
+            "queued",
+            "running",
+            "completed",
+            "failed",
+            "delayed",
+            "canceled",
+            "merged",
+            "skipped",
+        ],
+    );
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method, Request,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{consts::IDEMPOTENCY_KEY_HEADER, error::NovuError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyKey {
+    /// Sends the given key as-is.
+    Explicit(String),
+    /// Derives the key from the method, path and body of the request, so
+    /// that sending the same payload twice reuses the same key.
+    Derived,
+}
+
+/// Options applied to the requests sent through a client or service, e.g.
+/// `novu.with_options(options).trigger(payload)`.
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Sent with every mutating request, retries included, so that Novu
+    /// drops repeats of a request it has already handled. An explicit key
+    /// names one logical request: use a new one for each.
+    pub idempotency_key: Option<IdempotencyKey>,
+    pub headers: HeaderMap,
+    pub timeout: Option<Duration>,
+}
+
+impl RequestOptions {
+    pub fn builder() -> RequestOptionsBuilder {
+        RequestOptionsBuilder::default()
+    }
+
+    /// A copy of these options with another explicit key.
+    pub(crate) fn with_idempotency_key(&self, key: impl ToString) -> RequestOptions {
+        RequestOptions {
+            idempotency_key: Some(IdempotencyKey::Explicit(key.to_string())),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn apply(&self, request: &mut Request) -> Result<(), NovuError> {
+        for (name, value) in &self.headers {
+            request.headers_mut().insert(name, value.clone());
+        }
+
+        if let Some(timeout) = self.timeout {
+            *request.timeout_mut() = Some(timeout);
+        }
+
+        if matches!(*request.method(), Method::GET | Method::HEAD) {
+            return Ok(());
+        }
+
+        let key = match &self.idempotency_key {
+            Some(IdempotencyKey::Explicit(key)) => key.clone(),
+            Some(IdempotencyKey::Derived) => derive_idempotency_key(request),
+            None => return Ok(()),
+        };
+
+        request
+            .headers_mut()
+            .insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_str(&key)?);
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct RequestOptionsBuilder {
+    options: RequestOptions,
+}
+
+impl RequestOptionsBuilder {
+    pub fn new() -> RequestOptionsBuilder {
+        Self::default()
+    }
+
+    pub fn idempotency_key(mut self, key: impl ToString) -> RequestOptionsBuilder {
+        self.options.idempotency_key = Some(IdempotencyKey::Explicit(key.to_string()));
+        self
+    }
+
+    pub fn derive_idempotency_key(mut self) -> RequestOptionsBuilder {
+        self.options.idempotency_key = Some(IdempotencyKey::Derived);
+        self
+    }
+
+    pub fn header(
+        mut self,
+        name: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Result<RequestOptionsBuilder, NovuError> {
+        let name = HeaderName::from_bytes(name.as_ref().as_bytes())
+            .map_err(|_| NovuError::BuildError(format!("header name '{}'", name.as_ref())))?;
+
+        self.options
+            .headers
+            .insert(name, HeaderValue::from_str(value.as_ref())?);
+        Ok(self)
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> RequestOptionsBuilder {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    pub fn build(self) -> RequestOptions {
+        self.options
+    }
+}
+
+fn derive_idempotency_key(request: &Request) -> String {
+    let mut hasher = Sha256::new();
+
+    hasher.update(request.method().as_str());
+    hasher.update(b" ");
+    hasher.update(request.url().path());
+    if let Some(query) = request.url().query() {
+        hasher.update(b"?");
+        hasher.update(query);
+    }
+    hasher.update(b"\n");
+    if let Some(body) = request.body().and_then(|body| body.as_bytes()) {
+        // Re-serializing sorts object keys, so payloads built from a
+        // `HashMap` hash the same regardless of iteration order.
+        match serde_json::from_slice::<serde_json::Value>(body) {
+            Ok(value) => hasher.update(value.to_string()),
+            Err(_) => hasher.update(body),
+        }
+    }
+
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+#[test]
+fn test_apply_explicit_key_and_headers() {
+    let options = RequestOptions::builder()
+        .idempotency_key("job-42")
+        .header("X-Source", "billing")
+        .unwrap()
+        .timeout(Duration::from_secs(5))
+        .build();
+
+    let mut request = reqwest::Client::new()
+        .post("https://api.novu.co/v1/events/trigger")
+        .build()
+        .unwrap();
+    options.apply(&mut request).unwrap();
+
+    assert_eq!(request.headers()[IDEMPOTENCY_KEY_HEADER], "job-42");
+    assert_eq!(request.headers()["x-source"], "billing");
+    assert_eq!(request.timeout(), Some(&Duration::from_secs(5)));
+
+    let mut request = reqwest::Client::new()
+        .get("https://api.novu.co/v1/feeds")
+        .build()
+        .unwrap();
+    options.apply(&mut request).unwrap();
+
+    assert!(!request.headers().contains_key(IDEMPOTENCY_KEY_HEADER));
+    assert_eq!(request.headers()["x-source"], "billing");
+}
+
+#[cfg(test)]
+#[test]
+fn test_derived_key_is_deterministic() {
+    let options = RequestOptions::builder().derive_idempotency_key().build();
+    let key = |body: &str| {
+        let mut request = reqwest::Client::new()
+            .post("https://api.novu.co/v1/events/trigger")
+            .body(body.to_string())
+            .build()
+            .unwrap();
+        options.apply(&mut request).unwrap();
+        request.headers()[IDEMPOTENCY_KEY_HEADER].clone()
+    };
+
+    assert_eq!(key(r#"{"name":"sms"}"#), key(r#"{"name":"sms"}"#));
+    assert_eq!(
+        key(r#"{"name":"sms","payload":{"a":1,"b":2}}"#),
+        key(r#"{"payload":{"b":2,"a":1},"name":"sms"}"#)
+    );
+    assert_ne!(key(r#"{"name":"sms"}"#), key(r#"{"name":"email"}"#));
+    assert_eq!(key(r#"{"name":"sms"}"#).len(), 64);
+}
+
+#[cfg(test)]
+#[test]
+fn test_explicit_key_is_sent_with_every_request() {
+    let options = RequestOptions::builder().idempotency_key("job-42").build();
+    let clone = options.clone();
+    let post = || {
+        reqwest::Client::new()
+            .post("https://api.novu.co/v1/subscribers")
+            .build()
+            .unwrap()
+    };
+
+    // A retry through the same options must carry the key again.
+    for options in [&options, &clone] {
+        let mut request = post();
+        options.apply(&mut request).unwrap();
+        assert_eq!(request.headers()[IDEMPOTENCY_KEY_HEADER], "job-42");
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::VecDeque, future::Future};
+
+use futures::{
+    stream::{self, FuturesOrdered},
+    Stream, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::NovuError;
+
+/// One page of a paginated Novu list. Novu pages are zero-based, and
+/// depending on the endpoint the end is signalled by `has_more` or can be
+/// derived from `total_count`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub page: u32,
+    pub page_size: Option<u32>,
+    pub total_count: Option<u32>,
+    pub has_more: Option<bool>,
+    pub data: Vec<T>,
+}
+
+impl<T> Page<T> {
+    fn page_size(&self, requested: Option<u32>) -> Option<u32> {
+        self.page_size.or(requested).filter(|size| *size > 0)
+    }
+
+    /// The index of the last page, when the total count is known.
+    fn last_page(&self, requested: Option<u32>) -> Option<u32> {
+        let total_count = self.total_count?;
+        let page_size = self.page_size(requested)?;
+
+        Some((total_count.max(1) + page_size - 1) / page_size - 1)
+    }
+
+    fn is_last(&self, requested: Option<u32>) -> bool {
+        if self.data.is_empty() {
+            return true;
+        }
+
+        if let Some(has_more) = self.has_more {
+            return !has_more;
+        }
+
+        if let Some(last_page) = self.last_page(requested) {
+            return self.page >= last_page;
+        }
+
+        match self.page_size(requested) {
+            Some(page_size) => (self.data.len() as u32) < page_size,
+            None => false,
+        }
+    }
+}
+
+/// How `list_all`-style streams fetch their pages.
+#[derive(Debug, Clone, Copy)]
+pub struct PageOptions {
+    /// Items per page, or the server default when `None`.
+    pub page_size: Option<u32>,
+    /// How many pages may be requested ahead of the one being consumed.
+    /// `1` fetches strictly one page at a time.
+    pub prefetch: usize,
+}
+
+impl PageOptions {
+    pub fn new() -> PageOptions {
+        Self::default()
+    }
+
+    pub fn page_size(mut self, page_size: u32) -> PageOptions {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    pub fn prefetch(mut self, prefetch: usize) -> PageOptions {
+        self.prefetch = prefetch.max(1);
+        self
+    }
+}
+
+impl Default for PageOptions {
+    fn default() -> Self {
+        Self {
+            page_size: None,
+            prefetch: 1,
+        }
+    }
+}
+
+struct PaginationState<T, F, Fut: Future> {
+    fetch: F,
+    options: PageOptions,
+    next_page: u32,
+    last_page: Option<u32>,
+    in_flight: FuturesOrdered<Fut>,
+    buffer: VecDeque<T>,
+    done: bool,
+}
+
+/// Lazily walks every page produced by `fetch`, yielding the items one by
+/// one. Pages are only requested as the stream is polled, with at most
+/// `options.prefetch` requests in flight. The stream ends after the last
+/// page or the first error.
+pub fn paginate<T, F, Fut>(
+    options: PageOptions,
+    fetch: F,
+) -> impl Stream<Item = Result<T, NovuError>>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<Page<T>, NovuError>>,
+{
+    let state = PaginationState {
+        fetch,
+        options,
+        next_page: 0,
+        last_page: None,
+        in_flight: FuturesOrdered::new(),
+        buffer: VecDeque::new(),
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.buffer.pop_front() {
+                return Some((Ok(item), state));
+            }
+
+            if state.done {
+                return None;
+            }
+
+            while state.in_flight.len() < state.options.prefetch.max(1)
+                && state.last_page.map_or(true, |last| state.next_page <= last)
+            {
+                state.in_flight.push_back((state.fetch)(state.next_page));
+                state.next_page += 1;
+            }
+
+            match state.in_flight.next().await {
+                None => return None,
+                Some(Err(err)) => {
+                    state.done = true;
+                    state.in_flight = FuturesOrdered::new();
+                    return Some((Err(err), state));
+                }
+                Some(Ok(page)) => {
+                    if page.is_last(state.options.page_size) {
+                        state.done = true;
+                        state.in_flight = FuturesOrdered::new();
+                    } else if state.last_page.is_none() {
+                        state.last_page = page.last_page(state.options.page_size);
+                    }
+
+                    state.buffer.extend(page.data);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+fn test_page(page: u32, data: Vec<u32>) -> Page<u32> {
+    Page {
+        page,
+        page_size: Some(2),
+        total_count: None,
+        has_more: None,
+        data,
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_paginate_stops_on_has_more() {
+    let items: Vec<u32> = paginate(PageOptions::new(), |page| async move {
+        Ok(Page {
+            has_more: Some(page < 2),
+            ..test_page(page, vec![page * 2, page * 2 + 1])
+        })
+    })
+    .map(Result::unwrap)
+    .collect()
+    .await;
+
+    assert_eq!(items, vec![0, 1, 2, 3, 4, 5]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_paginate_stops_on_total_count() {
+    let requested = std::sync::Mutex::new(Vec::new());
+
+    let items: Vec<u32> = paginate(PageOptions::new().prefetch(4), |page| {
+        requested.lock().unwrap().push(page);
+        async move {
+            let data = (page * 2..(page * 2 + 2).min(5)).collect();
+            Ok(Page {
+                total_count: Some(5),
+                ..test_page(page, data)
+            })
+        }
+    })
+    .map(Result::unwrap)
+    .collect()
+    .await;
+
+    assert_eq!(items, vec![0, 1, 2, 3, 4]);
+    let requested = requested.lock().unwrap();
+    assert!(requested.len() <= 4);
+    assert_eq!(requested[..3], [0, 1, 2]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_paginate_stops_on_short_or_empty_page() {
+    let items: Vec<u32> = paginate(PageOptions::new(), |page| async move {
+        let data = match page {
+            0 => vec![0, 1],
+            1 => vec![2],
+            _ => unreachable!("requested a page after a short one"),
+        };
+        Ok(test_page(page, data))
+    })
+    .map(Result::unwrap)
+    .collect()
+    .await;
+    assert_eq!(items, vec![0, 1, 2]);
+
+    let items: Vec<u32> = paginate(PageOptions::new(), |page| async move {
+        Ok(Page {
+            page_size: None,
+            ..test_page(page, if page < 2 { vec![page] } else { vec![] })
+        })
+    })
+    .map(Result::unwrap)
+    .collect()
+    .await;
+    assert_eq!(items, vec![0, 1]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_paginate_prefetch_is_bounded() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let in_flight = AtomicUsize::new(0);
+    let max_in_flight = AtomicUsize::new(0);
+
+    let items: Vec<u32> = paginate(PageOptions::new().prefetch(3), |page| {
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+        let in_flight = &in_flight;
+        async move {
+            tokio::task::yield_now().await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(Page {
+                has_more: Some(page < 9),
+                ..test_page(page, vec![page])
+            })
+        }
+    })
+    .map(Result::unwrap)
+    .collect()
+    .await;
+
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_paginate_stops_after_error() {
+    let results: Vec<Result<u32, NovuError>> = paginate(PageOptions::new(), |page| async move {
+        match page {
+            0 => Ok(test_page(0, vec![0, 1])),
+            _ => Err(NovuError::Unauthorized("/subscribers".to_string())),
+        }
+    })
+    .collect()
+    .await;
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[2], Err(NovuError::Unauthorized(_))));
+}
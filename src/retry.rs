@@ -0,0 +1,289 @@
+use std::{
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use reqwest::{header::HeaderMap, Method};
+
+use crate::{consts::IDEMPOTENCY_KEY_HEADER, error::NovuError};
+
+type ErrorPredicate = Arc<dyn Fn(&NovuError) -> bool + Send + Sync>;
+
+/// Decides whether and when a failed request is sent again.
+///
+/// Delays grow exponentially from `base_delay` and are capped at `max_delay`.
+/// When Novu tells us how long to wait (`Retry-After` or `RateLimit-Reset`)
+/// that hint is used instead, and the request is given up on if the hint
+/// exceeds `max_delay`.
+///
+/// `POST` and `PATCH` requests are only retried when they carry an
+/// `Idempotency-Key` header, since sending them twice could otherwise trigger
+/// the same notification twice.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retryable_statuses: Vec<u16>,
+    retryable_error: ErrorPredicate,
+}
+
+impl RetryPolicy {
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::default()
+    }
+
+    /// A policy that sends every request exactly once.
+    pub fn disabled() -> Self {
+        RetryPolicyBuilder::new().max_attempts(1).build()
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    pub fn is_retryable_error(&self, err: &NovuError) -> bool {
+        (self.retryable_error)(err)
+    }
+
+    /// Whether a request with this method and these headers may be sent more
+    /// than once.
+    pub fn allows_retry(&self, method: &Method, headers: &HeaderMap) -> bool {
+        if self.max_attempts <= 1 {
+            return false;
+        }
+
+        match *method {
+            Method::POST | Method::PATCH => headers.contains_key(IDEMPOTENCY_KEY_HEADER),
+            _ => true,
+        }
+    }
+
+    /// The delay before the attempt following `attempt` (starting at 1), or
+    /// `None` when the server asked us to wait longer than `max_delay`.
+    pub fn delay(&self, attempt: u32, headers: Option<&HeaderMap>) -> Option<Duration> {
+        if let Some(hint) = headers.and_then(retry_after) {
+            return (hint <= self.max_delay).then_some(hint);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if !self.jitter {
+            return Some(delay);
+        }
+
+        let half = delay / 2;
+        let spread = fastrand::u64(0..=half.as_millis() as u64);
+
+        Some(half + Duration::from_millis(spread))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicyBuilder::new().build()
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("retryable_statuses", &self.retryable_statuses)
+            .finish()
+    }
+}
+
+pub struct RetryPolicyBuilder {
+    policy: RetryPolicy,
+}
+
+impl RetryPolicyBuilder {
+    pub fn new() -> RetryPolicyBuilder {
+        Self {
+            policy: RetryPolicy {
+                max_attempts: 3,
+                base_delay: Duration::from_millis(500),
+                max_delay: Duration::from_secs(30),
+                jitter: true,
+                retryable_statuses: vec![429, 502, 503, 504],
+                retryable_error: Arc::new(is_transient_error),
+            },
+        }
+    }
+
+    /// Total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> RetryPolicyBuilder {
+        self.policy.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> RetryPolicyBuilder {
+        self.policy.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> RetryPolicyBuilder {
+        self.policy.max_delay = max_delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicyBuilder {
+        self.policy.jitter = jitter;
+        self
+    }
+
+    pub fn retryable_statuses(mut self, statuses: impl Into<Vec<u16>>) -> RetryPolicyBuilder {
+        self.policy.retryable_statuses = statuses.into();
+        self
+    }
+
+    /// Decides which transport errors are retried. By default connection
+    /// failures and timeouts are.
+    pub fn retryable_error(
+        mut self,
+        predicate: impl Fn(&NovuError) -> bool + Send + Sync + 'static,
+    ) -> RetryPolicyBuilder {
+        self.policy.retryable_error = Arc::new(predicate);
+        self
+    }
+
+    pub fn build(self) -> RetryPolicy {
+        self.policy
+    }
+}
+
+impl Default for RetryPolicyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_transient_error(err: &NovuError) -> bool {
+    match err {
+        NovuError::HttpError(err) => err.is_connect() || err.is_timeout() || err.is_request(),
+        _ => false,
+    }
+}
+
+/// How long the server asked us to wait, from either `Retry-After` (seconds
+/// or an HTTP date) or `RateLimit-Reset` (seconds).
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name| headers.get(name)?.to_str().ok().map(str::trim);
+
+    if let Some(value) = header(reqwest::header::RETRY_AFTER.as_str()) {
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        if let Ok(date) = httpdate::parse_http_date(value) {
+            return Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or(Duration::ZERO),
+            );
+        }
+    }
+
+    header("ratelimit-reset")
+        .and_then(|value| value.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+#[test]
+fn test_retry_after_headers() {
+    use reqwest::header::HeaderValue;
+
+    let mut headers = HeaderMap::new();
+    assert_eq!(retry_after(&headers), None);
+
+    headers.insert("RateLimit-Reset", HeaderValue::from_static("7"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+    headers.insert(reqwest::header::RETRY_AFTER, HeaderValue::from_static("12"));
+    assert_eq!(retry_after(&headers), Some(Duration::from_secs(12)));
+
+    let past = httpdate::fmt_http_date(SystemTime::UNIX_EPOCH);
+    headers.insert(
+        reqwest::header::RETRY_AFTER,
+        HeaderValue::from_str(&past).unwrap(),
+    );
+    assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+}
+
+#[cfg(test)]
+#[test]
+fn test_backoff_delay() {
+    let policy = RetryPolicy::builder()
+        .base_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_millis(350))
+        .jitter(false)
+        .build();
+
+    assert_eq!(policy.delay(1, None), Some(Duration::from_millis(100)));
+    assert_eq!(policy.delay(2, None), Some(Duration::from_millis(200)));
+    assert_eq!(policy.delay(3, None), Some(Duration::from_millis(350)));
+    assert_eq!(policy.delay(40, None), Some(Duration::from_millis(350)));
+
+    let jittered = RetryPolicy::builder()
+        .base_delay(Duration::from_millis(100))
+        .build();
+    for _ in 0..100 {
+        let delay = jittered.delay(2, None).unwrap();
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_server_hint_overrides_backoff() {
+    use reqwest::header::HeaderValue;
+
+    let policy = RetryPolicy::builder()
+        .max_delay(Duration::from_secs(10))
+        .build();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(reqwest::header::RETRY_AFTER, HeaderValue::from_static("4"));
+    assert_eq!(
+        policy.delay(1, Some(&headers)),
+        Some(Duration::from_secs(4))
+    );
+
+    headers.insert(reqwest::header::RETRY_AFTER, HeaderValue::from_static("60"));
+    assert_eq!(policy.delay(1, Some(&headers)), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_non_idempotent_requests_need_a_key() {
+    use reqwest::header::HeaderValue;
+
+    let policy = RetryPolicy::default();
+    let mut headers = HeaderMap::new();
+
+    assert!(policy.allows_retry(&Method::GET, &headers));
+    assert!(policy.allows_retry(&Method::PUT, &headers));
+    assert!(policy.allows_retry(&Method::DELETE, &headers));
+    assert!(!policy.allows_retry(&Method::POST, &headers));
+    assert!(!policy.allows_retry(&Method::PATCH, &headers));
+
+    headers.insert(IDEMPOTENCY_KEY_HEADER, HeaderValue::from_static("key"));
+    assert!(policy.allows_retry(&Method::POST, &headers));
+    assert!(policy.allows_retry(&Method::PATCH, &headers));
+
+    assert!(!RetryPolicy::disabled().allows_retry(&Method::GET, &headers));
+}
@@ -1,43 +1,129 @@
+use futures::{stream, Stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
-    client::{Client, Response},
+    client::Client,
+    consts::{SUBSCRIBER_BULK_CONCURRENCY, SUBSCRIBER_BULK_MAX_SUBSCRIBERS},
     error::NovuError,
+    messages::{MarkMessagesAs, Message, MessageActionStatus, MessageActionType, MessageResponse},
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::{encode_path_segment, generate_query_string, string_enum},
+    workflows::PreferenceSettings,
+    ChannelTypeEnum,
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The fields to change on a subscriber; fields left as `None` keep their
+/// current value.
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscriberPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<HashMap<String, serde_json::Value>>,
     pub subscriber_id: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct SubscribersResponse {
-    pub page: i32,
-    pub total_count: i32,
-    pub page_size: i32,
-    pub data: Vec<String>,
-}
+pub type SubscribersResponse = Page<GetSubscriberResponse>;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSubscriberPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     pub subscriber_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<HashMap<String, serde_json::Value>>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSubscriberResponse {
+    pub acknowledged: bool,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkCreateSubscribersPayload<'a> {
+    subscribers: &'a [CreateSubscriberPayload],
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkSubscriberId {
+    subscriber_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkSubscriberRejection {
+    subscriber_id: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BulkCreateSubscribersBatch {
+    #[serde(default)]
+    created: Vec<BulkSubscriberId>,
+    #[serde(default)]
+    updated: Vec<BulkSubscriberId>,
+    #[serde(default)]
+    failed: Vec<BulkSubscriberRejection>,
+}
+
+#[derive(Debug)]
+pub enum BulkSubscriberError {
+    /// Novu refused this subscriber, with the reason it gave.
+    Rejected(String),
+    /// The request carrying this subscriber failed; the error is shared by
+    /// every subscriber in the same chunk.
+    Request(Arc<NovuError>),
+}
+
+#[derive(Debug)]
+pub struct BulkSubscriberFailure {
+    pub subscriber_id: String,
+    pub error: BulkSubscriberError,
+}
+
+/// The outcome of [`Subscribers::bulk_create`], by subscriber id.
+#[derive(Debug, Default)]
+pub struct BulkCreateSubscribersResponse {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub failed: Vec<BulkSubscriberFailure>,
+}
+
+impl BulkCreateSubscribersResponse {
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateSubscriberResponse {
@@ -124,23 +210,24 @@ pub struct UpdateCredentialsPayload {
     pub credentials: Credentials,
 }
 
-#[derive(Debug, Deserialize)]
-pub enum ProviderId {
-    Slack,
-    Discord,
-    MsTeams,
-    Mattermost,
-    Ryver,
-    Zulip,
-    GrafanaOnCall,
-    Getstream,
-    Fcm,
-    Apns,
-    Expo,
-    OneSignal,
-    Pushpad,
-    PushWebhook,
-    PusherBeam,
+string_enum! {
+    pub enum ProviderId {
+        Slack => "slack",
+        Discord => "discord",
+        MsTeams => "msteams",
+        Mattermost => "mattermost",
+        Ryver => "ryver",
+        Zulip => "zulip",
+        GrafanaOnCall => "grafana-on-call",
+        Getstream => "getstream",
+        Fcm => "fcm",
+        Apns => "apns",
+        Expo => "expo",
+        OneSignal => "one-signal",
+        Pushpad => "pushpad",
+        PushWebhook => "push-webhook",
+        PusherBeam => "pusher-beam",
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -182,31 +269,140 @@ pub struct UpdateCredentialsResponse {
     pub data: Option<HashMap<String, serde_json::Value>>,
 }
 
-impl serde::Serialize for ProviderId {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(match self {
-            ProviderId::Slack => "slack",
-            ProviderId::Discord => "discord",
-            ProviderId::MsTeams => "msteams",
-            ProviderId::Mattermost => "mattermost",
-            ProviderId::Ryver => "ryver",
-            ProviderId::Zulip => "zulip",
-            ProviderId::GrafanaOnCall => "grafana-on-call",
-            ProviderId::Getstream => "getstream",
-            ProviderId::Fcm => "fcm",
-            ProviderId::Apns => "apns",
-            ProviderId::Expo => "expo",
-            ProviderId::OneSignal => "one-signal",
-            ProviderId::Pushpad => "pushpad",
-            ProviderId::PushWebhook => "push-webhook",
-            ProviderId::PusherBeam => "pusher-beam",
-        })
+/// A subscriber's preference for one workflow.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriberPreference {
+    pub template: PreferenceTemplate,
+    pub preference: Preference,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreferenceTemplate {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub name: String,
+    /// Critical workflows are sent regardless of the subscriber's preference.
+    #[serde(default)]
+    pub critical: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preference {
+    pub enabled: bool,
+    #[serde(default)]
+    pub channels: PreferenceSettings,
+}
+
+/// A subscriber's preference across all workflows.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalPreference {
+    pub preference: Preference,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum GlobalPreferenceResponse {
+    One(GlobalPreference),
+    Many(Vec<GlobalPreference>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelPreference {
+    #[serde(rename = "type")]
+    pub channel_type: ChannelTypeEnum,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdatePreferencePayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<ChannelPreference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+}
+
+/// Changes the global preference; channels that aren't listed keep their
+/// current setting.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGlobalPreferencesPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub preferences: Vec<ChannelPreference>,
+}
+
+/// Narrows down [`Subscribers::feed`]. Unset fields are left to Novu's
+/// defaults.
+#[derive(Debug, Clone, Default)]
+pub struct FeedQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub seen: Option<bool>,
+    pub read: Option<bool>,
+    /// Only messages of the feed with this identifier.
+    pub feed_identifier: Option<String>,
+}
+
+impl FeedQuery {
+    fn query_string(&self) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+
+        if let Some(page) = self.page {
+            query.append_pair("page", &page.to_string());
+        }
+        if let Some(limit) = self.limit {
+            query.append_pair("limit", &limit.to_string());
+        }
+        if let Some(seen) = self.seen {
+            query.append_pair("seen", &seen.to_string());
+        }
+        if let Some(read) = self.read {
+            query.append_pair("read", &read.to_string());
+        }
+        if let Some(feed_identifier) = &self.feed_identifier {
+            query.append_pair("feedIdentifier", feed_identifier);
+        }
+
+        query.finish()
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnseenCountResponse {
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MarkMessagesPayload<'a> {
+    message_id: &'a [String],
+    mark_as: MarkMessagesAs,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MarkAllMessagesPayload<'a> {
+    mark_as: MarkMessagesAs,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_identifier: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageActionPayload {
+    status: MessageActionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<serde_json::Value>,
+}
+
 #[derive(Clone)]
 pub struct Subscribers {
     client: Client,
@@ -217,21 +413,36 @@ impl Subscribers {
         Self { client }
     }
 
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
     pub async fn list(&self, page: i32) -> Result<SubscribersResponse, NovuError> {
         let endpoint = format!("/subscribers/?page={}", page);
-        let result: Response<SubscribersResponse> = self.client.get(endpoint).await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => Err(NovuError::UnexpectedResponse {
-                msg: err.message,
-                code: err.status_code.to_string(),
-            }),
-            crate::client::Response::Messages(err) => Err(NovuError::UnexpectedResponse {
-                msg: format!("{:?}", err.message),
-                code: err.status_code.to_string(),
-            }),
-        }
+        self.client.get_paginated(endpoint).await
+    }
+
+    /// Streams every subscriber, fetching pages as the stream is polled.
+    pub fn list_all(
+        &self,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<GetSubscriberResponse, NovuError>> {
+        let subscribers = self.clone();
+
+        paginate(options, move |page| {
+            let subscribers = subscribers.clone();
+            async move {
+                let mut params = HashMap::new();
+                params.insert("page", Some(page));
+                params.insert("limit", options.page_size);
+
+                subscribers
+                    .client
+                    .get_paginated(format!("/subscribers/?{}", generate_query_string(&params)))
+                    .await
+            }
+        })
     }
 
     pub async fn get_subscriber(
@@ -239,19 +450,7 @@ impl Subscribers {
         subscriber_id: String,
     ) -> Result<GetSubscriberResponse, NovuError> {
         let endpoint = format!("/subscribers/{}", subscriber_id);
-        let result = self.client.get(endpoint).await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => Err(NovuError::UnexpectedResponse {
-                msg: err.message,
-                code: err.status_code.to_string(),
-            }),
-            crate::client::Response::Messages(err) => Err(NovuError::UnexpectedResponse {
-                msg: format!("{:?}", err.message),
-                code: err.status_code.to_string(),
-            }),
-        }
+        self.client.get(endpoint).await
     }
 
     pub async fn update(
@@ -260,58 +459,782 @@ impl Subscribers {
         data: SubscriberPayload,
     ) -> Result<UpdateSubscriberResponse, NovuError> {
         let endpoint = format!("/subscribers/{}", subscriber_id);
-        let result = self.client.put(endpoint, &data).await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => Err(NovuError::UnexpectedResponse {
-                msg: err.message,
-                code: err.status_code.to_string(),
-            }),
-            crate::client::Response::Messages(err) => Err(NovuError::UnexpectedResponse {
-                msg: format!("{:?}", err.message),
-                code: err.status_code.to_string(),
-            }),
-        }
+        self.client.put(endpoint, &data).await
     }
 
+    /// Creates the subscriber, or updates it if one with the same
+    /// `subscriber_id` already exists, so it is safe to call repeatedly.
     pub async fn create(
         &self,
         data: CreateSubscriberPayload,
     ) -> Result<CreateSubscriberResponse, NovuError> {
         let endpoint = "/subscribers/".to_string();
-        let result = self.client.post(endpoint, Some(&data)).await?;
-
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => Err(NovuError::UnexpectedResponse {
-                msg: err.message,
-                code: err.status_code.to_string(),
-            }),
-            crate::client::Response::Messages(err) => Err(NovuError::UnexpectedResponse {
-                msg: format!("{:?}", err.message),
-                code: err.status_code.to_string(),
-            }),
+        self.client.post(endpoint, Some(&data)).await
+    }
+
+    /// Creates the subscriber, or updates the one with the same
+    /// `subscriber_id`. Same as [`Subscribers::create`], for call sites that
+    /// mean to upsert.
+    pub async fn upsert(
+        &self,
+        data: CreateSubscriberPayload,
+    ) -> Result<CreateSubscriberResponse, NovuError> {
+        self.create(data).await
+    }
+
+    /// Creates or updates every subscriber, split into as many requests as
+    /// Novu's per-request limit requires.
+    pub async fn bulk_create(
+        &self,
+        subscribers: Vec<CreateSubscriberPayload>,
+    ) -> BulkCreateSubscribersResponse {
+        let chunks: Vec<_> = subscribers
+            .chunks(SUBSCRIBER_BULK_MAX_SUBSCRIBERS)
+            .collect();
+        let clients = self.client.split_idempotency_key(chunks.len());
+
+        let outcomes: Vec<_> = stream::iter(chunks.into_iter().zip(clients))
+            .map(|(chunk, client)| async move {
+                let payload = BulkCreateSubscribersPayload { subscribers: chunk };
+                let result: Result<BulkCreateSubscribersBatch, NovuError> =
+                    client.post("/subscribers/bulk", Some(&payload)).await;
+                (chunk, result)
+            })
+            .buffered(SUBSCRIBER_BULK_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut response = BulkCreateSubscribersResponse::default();
+        for (chunk, outcome) in outcomes {
+            match outcome {
+                Ok(batch) => {
+                    response
+                        .created
+                        .extend(batch.created.into_iter().map(|s| s.subscriber_id));
+                    response
+                        .updated
+                        .extend(batch.updated.into_iter().map(|s| s.subscriber_id));
+                    response.failed.extend(batch.failed.into_iter().map(|s| {
+                        BulkSubscriberFailure {
+                            subscriber_id: s.subscriber_id,
+                            error: BulkSubscriberError::Rejected(s.message),
+                        }
+                    }));
+                }
+                Err(err) => {
+                    let err = Arc::new(err);
+                    response
+                        .failed
+                        .extend(chunk.iter().map(|subscriber| BulkSubscriberFailure {
+                            subscriber_id: subscriber.subscriber_id.clone(),
+                            error: BulkSubscriberError::Request(err.clone()),
+                        }));
+                }
+            }
+        }
+
+        response
+    }
+
+    pub async fn delete(
+        &self,
+        subscriber_id: impl AsRef<str>,
+    ) -> Result<DeleteSubscriberResponse, NovuError> {
+        self.client
+            .delete(format!(
+                "/subscribers/{}",
+                encode_path_segment(subscriber_id.as_ref())
+            ))
+            .await
+    }
+
+    /// The subscriber's preference for every workflow.
+    pub async fn preferences(
+        &self,
+        subscriber_id: impl AsRef<str>,
+    ) -> Result<Vec<SubscriberPreference>, NovuError> {
+        self.client
+            .get(format!(
+                "/subscribers/{}/preferences",
+                encode_path_segment(subscriber_id.as_ref())
+            ))
+            .await
+    }
+
+    /// Enables or disables one channel of a workflow, or the whole workflow
+    /// when `channel` is `None`.
+    pub async fn update_preference(
+        &self,
+        subscriber_id: impl AsRef<str>,
+        workflow_id: impl AsRef<str>,
+        channel: Option<ChannelTypeEnum>,
+        enabled: bool,
+    ) -> Result<SubscriberPreference, NovuError> {
+        let payload = match channel {
+            Some(channel_type) => UpdatePreferencePayload {
+                channel: Some(ChannelPreference {
+                    channel_type,
+                    enabled,
+                }),
+                enabled: None,
+            },
+            None => UpdatePreferencePayload {
+                channel: None,
+                enabled: Some(enabled),
+            },
+        };
+
+        self.client
+            .patch(
+                format!(
+                    "/subscribers/{}/preferences/{}",
+                    encode_path_segment(subscriber_id.as_ref()),
+                    encode_path_segment(workflow_id.as_ref())
+                ),
+                Some(&payload),
+            )
+            .await
+    }
+
+    pub async fn global_preferences(
+        &self,
+        subscriber_id: impl AsRef<str>,
+    ) -> Result<GlobalPreference, NovuError> {
+        let endpoint = format!(
+            "/subscribers/{}/preferences/global",
+            encode_path_segment(subscriber_id.as_ref())
+        );
+        let response: GlobalPreferenceResponse = self.client.get(&endpoint).await?;
+
+        match response {
+            GlobalPreferenceResponse::One(preference) => Ok(preference),
+            GlobalPreferenceResponse::Many(preferences) => preferences
+                .into_iter()
+                .next()
+                .ok_or_else(|| NovuError::NotFound {
+                    path: endpoint,
+                    message: "no global preference".to_string(),
+                }),
         }
     }
 
+    pub async fn update_global_preferences(
+        &self,
+        subscriber_id: impl AsRef<str>,
+        data: UpdateGlobalPreferencesPayload,
+    ) -> Result<GlobalPreference, NovuError> {
+        self.client
+            .patch(
+                format!(
+                    "/subscribers/{}/preferences",
+                    encode_path_segment(subscriber_id.as_ref())
+                ),
+                Some(&data),
+            )
+            .await
+    }
+
+    /// The subscriber's in-app messages, newest first.
+    pub async fn feed(
+        &self,
+        subscriber_id: impl AsRef<str>,
+        query: &FeedQuery,
+    ) -> Result<MessageResponse, NovuError> {
+        self.client
+            .get_paginated(format!(
+                "/subscribers/{}/notifications/feed?{}",
+                encode_path_segment(subscriber_id.as_ref()),
+                query.query_string()
+            ))
+            .await
+    }
+
+    /// How many of the subscriber's in-app messages haven't been seen.
+    pub async fn unseen_count(&self, subscriber_id: impl AsRef<str>) -> Result<u64, NovuError> {
+        let response: UnseenCountResponse = self
+            .client
+            .get(format!(
+                "/subscribers/{}/notifications/unseen?seen=false",
+                encode_path_segment(subscriber_id.as_ref())
+            ))
+            .await?;
+
+        Ok(response.count)
+    }
+
+    pub async fn mark_as(
+        &self,
+        subscriber_id: impl AsRef<str>,
+        mark_as: MarkMessagesAs,
+        message_ids: &[String],
+    ) -> Result<Vec<Message>, NovuError> {
+        let payload = MarkMessagesPayload {
+            message_id: message_ids,
+            mark_as,
+        };
+
+        self.client
+            .post(
+                format!(
+                    "/subscribers/{}/messages/mark-as",
+                    encode_path_segment(subscriber_id.as_ref())
+                ),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// Marks every message, or every message of one feed, and returns how
+    /// many were changed.
+    pub async fn mark_all(
+        &self,
+        subscriber_id: impl AsRef<str>,
+        mark_as: MarkMessagesAs,
+        feed_identifier: Option<&str>,
+    ) -> Result<u64, NovuError> {
+        let payload = MarkAllMessagesPayload {
+            mark_as,
+            feed_identifier,
+        };
+
+        self.client
+            .post(
+                format!(
+                    "/subscribers/{}/messages/mark-all",
+                    encode_path_segment(subscriber_id.as_ref())
+                ),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// Records that the subscriber clicked one of a message's buttons.
+    pub async fn message_action(
+        &self,
+        subscriber_id: impl AsRef<str>,
+        message_id: impl AsRef<str>,
+        action_type: MessageActionType,
+        status: MessageActionStatus,
+        payload: Option<serde_json::Value>,
+    ) -> Result<Message, NovuError> {
+        let data = MessageActionPayload { status, payload };
+
+        self.client
+            .post(
+                format!(
+                    "/subscribers/{}/messages/{}/actions/{}",
+                    encode_path_segment(subscriber_id.as_ref()),
+                    encode_path_segment(message_id.as_ref()),
+                    action_type
+                ),
+                Some(&data),
+            )
+            .await
+    }
+
     pub async fn update_credentials(
         &self,
         subscriber_id: String,
         data: UpdateCredentialsPayload,
     ) -> Result<UpdateCredentialsResponse, NovuError> {
         let endpoint = format!("/subscribers/{}/credentials", subscriber_id);
-        let result = self.client.put(endpoint, &data).await?;
-        match result {
-            crate::client::Response::Success(data) => Ok(data.data),
-            crate::client::Response::Error(err) => Err(NovuError::UnexpectedResponse {
-                msg: err.message,
-                code: err.status_code.to_string(),
-            }),
-            crate::client::Response::Messages(err) => Err(NovuError::UnexpectedResponse {
-                msg: format!("{:?}", err.message),
-                code: err.status_code.to_string(),
-            }),
-        }
+        self.client.put(endpoint, &data).await
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_list_all_subscribers() {
+    use futures::StreamExt;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    for (page, ids) in [(0, vec!["a", "b"]), (1, vec!["c"])] {
+        let data: Vec<_> = ids
+            .iter()
+            .map(|id| serde_json::json!({ "subscriberId": id, "deleted": false }))
+            .collect();
+
+        Mock::given(method("GET"))
+            .and(path("/v1/subscribers/"))
+            .and(query_param("page", page.to_string()))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "page": page,
+                "pageSize": 2,
+                "totalCount": 3,
+                "data": data,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let subscribers = Subscribers::new(Client::new("", Some(server.uri())).unwrap());
+    let ids: Vec<_> = subscribers
+        .list_all(PageOptions::new().page_size(2))
+        .map(|subscriber| subscriber.unwrap().subscriber_id.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(ids, vec!["a", "b", "c"]);
+}
+
+#[cfg(test)]
+#[test]
+fn test_provider_id_round_trip() {
+    crate::utils::assert_string_enum(
+        ProviderId::ALL,
+        &[
+            "slack",
+            "discord",
+            "msteams",
+            "mattermost",
+            "ryver",
+            "zulip",
+            "grafana-on-call",
+            "getstream",
+            "fcm",
+            "apns",
+            "expo",
+            "one-signal",
+            "pushpad",
+            "push-webhook",
+            "pusher-beam",
+        ],
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_update_payload_skips_unset_fields() {
+    let payload = SubscriberPayload {
+        subscriber_id: "ada".to_string(),
+        email: Some("ada@example.com".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_json::to_value(&payload).unwrap(),
+        serde_json::json!({ "subscriberId": "ada", "email": "ada@example.com" })
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_bulk_create_chunks_and_reports_ids() {
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/subscribers/bulk"))
+        .respond_with(|request: &Request| {
+            let body: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+            let ids: Vec<String> = body["subscribers"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|s| s["subscriberId"].as_str().unwrap().to_string())
+                .collect();
+
+            // The second chunk fails as a whole.
+            if ids[0] == "user-500" {
+                return ResponseTemplate::new(500);
+            }
+
+            let created: Vec<_> = ids[1..]
+                .iter()
+                .map(|id| serde_json::json!({ "subscriberId": id }))
+                .collect();
+            ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": {
+                    "created": created,
+                    "updated": [{ "subscriberId": ids[0] }],
+                    "failed": [],
+                },
+            }))
+        })
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let subscribers = Subscribers::new(Client::new("", Some(server.uri())).unwrap());
+    let payloads: Vec<_> = (0..1001)
+        .map(|i| CreateSubscriberPayload {
+            subscriber_id: format!("user-{}", i),
+            ..Default::default()
+        })
+        .collect();
+
+    let response = subscribers.bulk_create(payloads).await;
+    assert!(!response.is_success());
+    assert_eq!(response.updated, ["user-0", "user-1000"]);
+    assert_eq!(response.created.len(), 499);
+    assert_eq!(response.failed.len(), 500);
+    assert_eq!(response.failed[0].subscriber_id, "user-500");
+    assert!(matches!(
+        response.failed[0].error,
+        BulkSubscriberError::Request(_)
+    ));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_bulk_create_splits_idempotency_key() {
+    use std::sync::Mutex;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, Request, ResponseTemplate,
+    };
+
+    let keys = Arc::new(Mutex::new(Vec::new()));
+    let seen = keys.clone();
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/subscribers/bulk"))
+        .respond_with(move |request: &Request| {
+            let (_, key) = request
+                .headers
+                .iter()
+                .find(|(name, _)| {
+                    name.as_str()
+                        .eq_ignore_ascii_case(crate::consts::IDEMPOTENCY_KEY_HEADER)
+                })
+                .unwrap();
+            seen.lock().unwrap().push(key.last().as_str().to_string());
+
+            ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "data": { "created": [], "updated": [], "failed": [] },
+            }))
+        })
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = Client::new("", Some(server.uri())).unwrap().with_options(
+        RequestOptions::builder()
+            .idempotency_key("import-1")
+            .build(),
+    );
+    let payloads: Vec<_> = (0..501)
+        .map(|i| CreateSubscriberPayload {
+            subscriber_id: format!("user-{}", i),
+            ..Default::default()
+        })
+        .collect();
+    Subscribers::new(client).bulk_create(payloads).await;
+
+    let mut keys = keys.lock().unwrap().clone();
+    keys.sort();
+    assert_eq!(keys, ["import-1-0", "import-1-1"]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_upsert_and_delete_subscriber() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/subscribers/"))
+        .and(body_json(
+            serde_json::json!({ "subscriberId": "team/ada", "firstName": "Ada" }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "data": { "subscriberId": "team/ada", "firstName": "Ada", "deleted": false },
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/subscribers/team%2Fada"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "acknowledged": true, "status": "deleted" },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let subscribers = Subscribers::new(Client::new("", Some(server.uri())).unwrap());
+    let payload = CreateSubscriberPayload {
+        subscriber_id: "team/ada".to_string(),
+        first_name: Some("Ada".to_string()),
+        ..Default::default()
+    };
+    for _ in 0..2 {
+        let subscriber = subscribers.upsert(payload.clone()).await.unwrap();
+        assert_eq!(subscriber.first_name.as_deref(), Some("Ada"));
     }
+
+    let deleted = subscribers.delete("team/ada").await.unwrap();
+    assert!(deleted.acknowledged);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_subscriber_preferences() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let preference = |email: bool| {
+        serde_json::json!({
+            "template": { "_id": "workflow-1", "name": "Invoice paid", "critical": false },
+            "preference": {
+                "enabled": true,
+                "channels": { "email": email, "in_app": true },
+            },
+        })
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscribers/ada%20lovelace/preferences"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "data": [preference(true)] })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path(
+            "/v1/subscribers/ada%20lovelace/preferences/team%2Fworkflow-1",
+        ))
+        .and(body_json(
+            serde_json::json!({ "channel": { "type": "email", "enabled": false } }),
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({ "data": preference(false) })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let subscribers = Subscribers::new(Client::new("", Some(server.uri())).unwrap());
+    let preferences = subscribers.preferences("ada lovelace").await.unwrap();
+    assert_eq!(preferences[0].template.name, "Invoice paid");
+    assert!(preferences[0]
+        .preference
+        .channels
+        .is_enabled(&ChannelTypeEnum::EMAIL));
+    // Channels missing from the response are enabled.
+    assert!(preferences[0].preference.channels.push);
+
+    let updated = subscribers
+        .update_preference(
+            "ada lovelace",
+            "team/workflow-1",
+            Some(ChannelTypeEnum::EMAIL),
+            false,
+        )
+        .await
+        .unwrap();
+    assert!(!updated
+        .preference
+        .channels
+        .is_enabled(&ChannelTypeEnum::EMAIL));
+    assert!(updated
+        .preference
+        .channels
+        .is_enabled(&ChannelTypeEnum::InApp));
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_global_preferences() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscribers/ada/preferences/global"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": [{ "preference": { "enabled": true, "channels": { "sms": false } } }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/subscribers/ada/preferences"))
+        .and(body_json(serde_json::json!({
+            "preferences": [{ "type": "sms", "enabled": true }],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "preference": { "enabled": true, "channels": { "sms": true } } },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let subscribers = Subscribers::new(Client::new("", Some(server.uri())).unwrap());
+    let global = subscribers.global_preferences("ada").await.unwrap();
+    assert!(!global.preference.channels.sms);
+
+    let updated = subscribers
+        .update_global_preferences(
+            "ada",
+            UpdateGlobalPreferencesPayload {
+                preferences: vec![ChannelPreference {
+                    channel_type: ChannelTypeEnum::SMS,
+                    enabled: true,
+                }],
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(updated.preference.channels.sms);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_in_app_feed() {
+    use wiremock::{
+        matchers::{body_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let message = |read: bool| {
+        serde_json::json!({
+            "_id": "message-1",
+            "channel": "in_app",
+            "content": "Your invoice was paid",
+            "seen": true,
+            "read": read,
+            "cta": {
+                "type": "redirect",
+                "data": { "url": "/invoices/42" },
+                "action": {
+                    "status": "pending",
+                    "buttons": [{ "type": "primary", "content": "View" }],
+                },
+            },
+        })
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscribers/ada%20lovelace/notifications/feed"))
+        .and(query_param("page", "0"))
+        .and(query_param("read", "false"))
+        .and(query_param("feedIdentifier", "billing"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 10,
+            "totalCount": 1,
+            "hasMore": false,
+            "data": [message(false)],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/subscribers/ada%20lovelace/notifications/unseen"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({ "data": { "count": 3 } })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/subscribers/ada%20lovelace/messages/mark-as"))
+        .and(body_json(
+            serde_json::json!({ "messageId": ["message-1"], "markAs": "read" }),
+        ))
+        .respond_with(
+            ResponseTemplate::new(201)
+                .set_body_json(serde_json::json!({ "data": [message(true)] })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/subscribers/ada%20lovelace/messages/mark-all"))
+        .and(body_json(
+            serde_json::json!({ "markAs": "seen", "feedIdentifier": "billing" }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "data": 4 })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path(
+            "/v1/subscribers/ada%20lovelace/messages/message%2F1/actions/primary",
+        ))
+        .and(body_json(
+            serde_json::json!({ "status": "done", "payload": { "approved": true } }),
+        ))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(serde_json::json!({ "data": message(true) })),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let subscribers = Subscribers::new(Client::new("", Some(server.uri())).unwrap());
+    let feed = subscribers
+        .feed(
+            "ada lovelace",
+            &FeedQuery {
+                page: Some(0),
+                read: Some(false),
+                feed_identifier: Some("billing".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let cta = feed.data[0].cta.as_ref().unwrap();
+    assert_eq!(
+        cta.data.as_ref().unwrap().url.as_deref(),
+        Some("/invoices/42")
+    );
+    assert_eq!(
+        cta.action.as_ref().unwrap().buttons[0].button_type,
+        MessageActionType::Primary
+    );
+
+    assert_eq!(subscribers.unseen_count("ada lovelace").await.unwrap(), 3);
+
+    let marked = subscribers
+        .mark_as(
+            "ada lovelace",
+            MarkMessagesAs::Read,
+            &["message-1".to_string()],
+        )
+        .await
+        .unwrap();
+    assert!(marked[0].read);
+
+    let count = subscribers
+        .mark_all("ada lovelace", MarkMessagesAs::Seen, Some("billing"))
+        .await
+        .unwrap();
+    assert_eq!(count, 4);
+
+    subscribers
+        .message_action(
+            "ada lovelace",
+            "message/1",
+            MessageActionType::Primary,
+            MessageActionStatus::Done,
+            Some(serde_json::json!({ "approved": true })),
+        )
+        .await
+        .unwrap();
 }
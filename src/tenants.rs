@@ -0,0 +1,396 @@
+use std::{collections::HashMap, fmt};
+
+use futures::Stream;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    client::Client,
+    error::NovuError,
+    events::TriggerTenant,
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::{encode_path_segment, generate_query_string},
+};
+
+/// The identifier a tenant is created with and referenced by, both by the
+/// [`Tenants`] service and when triggering a workflow for a tenant.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TenantIdentifier(String);
+
+impl TenantIdentifier {
+    pub fn new(identifier: impl ToString) -> Self {
+        Self(identifier.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for TenantIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for TenantIdentifier {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for TenantIdentifier {
+    fn from(identifier: &str) -> Self {
+        Self::new(identifier)
+    }
+}
+
+impl From<String> for TenantIdentifier {
+    fn from(identifier: String) -> Self {
+        Self(identifier)
+    }
+}
+
+/// A tenant, with `data` decoded as `D`; by default any JSON object.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tenant<D = HashMap<String, Value>> {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_environmentId")]
+    pub environment_id: Option<String>,
+    pub identifier: TenantIdentifier,
+    pub name: Option<String>,
+    pub data: Option<D>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl<D> From<&Tenant<D>> for TriggerTenant {
+    fn from(tenant: &Tenant<D>) -> Self {
+        TriggerTenant::Identifier(tenant.identifier.clone())
+    }
+}
+
+pub type TenantsResponse<D = HashMap<String, Value>> = Page<Tenant<D>>;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTenantPayload<D = HashMap<String, Value>> {
+    pub identifier: TenantIdentifier,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<D>,
+}
+
+/// Only the fields that are set are changed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTenantPayload<D = HashMap<String, Value>> {
+    /// Renames the tenant's identifier.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<TenantIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<D>,
+}
+
+impl<D> Default for UpdateTenantPayload<D> {
+    fn default() -> Self {
+        Self {
+            identifier: None,
+            name: None,
+            data: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Tenants {
+    client: Client,
+}
+
+impl Tenants {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
+    pub async fn create<D: Serialize + DeserializeOwned>(
+        &self,
+        payload: &CreateTenantPayload<D>,
+    ) -> Result<Tenant<D>, NovuError> {
+        self.client.post("/tenants", Some(payload)).await
+    }
+
+    /// Lists tenants whose data is any JSON object. Use
+    /// [`Tenants::list_with_data`] to decode typed data.
+    pub async fn list(
+        &self,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<TenantsResponse, NovuError> {
+        self.list_with_data(page, limit).await
+    }
+
+    /// Like [`Tenants::list`], with `data` decoded as `D`.
+    pub async fn list_with_data<D: DeserializeOwned>(
+        &self,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<TenantsResponse<D>, NovuError> {
+        let mut params = HashMap::new();
+        params.insert("page", page.map(|p| p.to_string()));
+        params.insert("limit", limit.map(|l| l.to_string()));
+
+        self.client
+            .get_paginated(format!("/tenants?{}", generate_query_string(&params)))
+            .await
+    }
+
+    /// Streams every tenant, fetching pages as the stream is polled.
+    pub fn list_all(&self, options: PageOptions) -> impl Stream<Item = Result<Tenant, NovuError>> {
+        self.list_all_with_data(options)
+    }
+
+    /// Like [`Tenants::list_all`], with `data` decoded as `D`.
+    pub fn list_all_with_data<D: DeserializeOwned>(
+        &self,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<Tenant<D>, NovuError>> {
+        let tenants = self.clone();
+
+        paginate(options, move |page| {
+            let tenants = tenants.clone();
+            async move { tenants.list_with_data(Some(page), options.page_size).await }
+        })
+    }
+
+    pub async fn get(&self, identifier: impl AsRef<str>) -> Result<Tenant, NovuError> {
+        self.get_with_data(identifier).await
+    }
+
+    /// Like [`Tenants::get`], with `data` decoded as `D`.
+    pub async fn get_with_data<D: DeserializeOwned>(
+        &self,
+        identifier: impl AsRef<str>,
+    ) -> Result<Tenant<D>, NovuError> {
+        self.client
+            .get(format!(
+                "/tenants/{}",
+                encode_path_segment(identifier.as_ref())
+            ))
+            .await
+    }
+
+    /// Updates a tenant whose data is any JSON object. Use
+    /// [`Tenants::update_with_data`] to set typed data.
+    pub async fn update(
+        &self,
+        identifier: impl AsRef<str>,
+        payload: &UpdateTenantPayload,
+    ) -> Result<Tenant, NovuError> {
+        self.update_with_data(identifier, payload).await
+    }
+
+    /// Like [`Tenants::update`], with `data` encoded from and decoded as `D`.
+    pub async fn update_with_data<D: Serialize + DeserializeOwned>(
+        &self,
+        identifier: impl AsRef<str>,
+        payload: &UpdateTenantPayload<D>,
+    ) -> Result<Tenant<D>, NovuError> {
+        self.client
+            .patch(
+                format!("/tenants/{}", encode_path_segment(identifier.as_ref())),
+                Some(payload),
+            )
+            .await
+    }
+
+    pub async fn delete(&self, identifier: impl AsRef<str>) -> Result<(), NovuError> {
+        self.client
+            .delete(format!(
+                "/tenants/{}",
+                encode_path_segment(identifier.as_ref())
+            ))
+            .await
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_tenant_lifecycle() {
+    use wiremock::{
+        matchers::{body_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Branding {
+        support_email: String,
+    }
+
+    let tenant = |name: &str, email: &str| {
+        serde_json::json!({
+            "_id": "tenant-1",
+            "_environmentId": "env-1",
+            "identifier": "brand-a",
+            "name": name,
+            "data": { "supportEmail": email },
+        })
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/tenants"))
+        .and(body_json(serde_json::json!({
+            "identifier": "brand-a",
+            "name": "Brand A",
+            "data": { "supportEmail": "help@brand-a.com" },
+        })))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_json(
+                serde_json::json!({ "data": tenant("Brand A", "help@brand-a.com") }),
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/tenants"))
+        .and(query_param("page", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 10,
+            "hasMore": false,
+            "data": [tenant("Brand A", "help@brand-a.com")],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/tenants/brand-a"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "data": tenant("Brand A", "help@brand-a.com") }),
+            ),
+        )
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/tenants/brand-a"))
+        .and(body_json(serde_json::json!({ "name": "Brand A Bank" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(
+            serde_json::json!({ "data": tenant("Brand A Bank", "help@brand-a.com") }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/tenants/brand%2Fb"))
+        .and(body_json(serde_json::json!({
+            "data": { "supportEmail": "help@brand-b.com" },
+        })))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "data": tenant("Brand B", "help@brand-b.com") }),
+            ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/tenants/brand-a"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let created = novu
+        .tenants
+        .create(&CreateTenantPayload {
+            identifier: "brand-a".into(),
+            name: "Brand A".to_string(),
+            data: Some(Branding {
+                support_email: "help@brand-a.com".to_string(),
+            }),
+        })
+        .await
+        .unwrap();
+    assert_eq!(created.identifier, TenantIdentifier::new("brand-a"));
+    assert_eq!(
+        created.data.unwrap().support_email,
+        "help@brand-a.com".to_string()
+    );
+
+    let page = novu.tenants.list(Some(0), None).await.unwrap();
+    assert_eq!(
+        page.data[0].data.as_ref().unwrap()["supportEmail"],
+        "help@brand-a.com"
+    );
+
+    let tenant = novu.tenants.get("brand-a").await.unwrap();
+    assert_eq!(tenant.data.unwrap()["supportEmail"], "help@brand-a.com");
+    let tenant = novu
+        .tenants
+        .get_with_data::<Branding>("brand-a")
+        .await
+        .unwrap();
+    assert_eq!(tenant.data.unwrap().support_email, "help@brand-a.com");
+
+    let updated = novu
+        .tenants
+        .update(
+            "brand-a",
+            &UpdateTenantPayload {
+                name: Some("Brand A Bank".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.name.as_deref(), Some("Brand A Bank"));
+
+    let updated = novu
+        .tenants
+        .update_with_data(
+            "brand/b",
+            &UpdateTenantPayload {
+                data: Some(Branding {
+                    support_email: "help@brand-b.com".to_string(),
+                }),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        updated.data.unwrap().support_email,
+        "help@brand-b.com".to_string()
+    );
+
+    novu.tenants.delete("brand-a").await.unwrap();
+}
+
+#[cfg(test)]
+#[test]
+fn test_tenant_identifier_in_trigger() {
+    let tenant: Tenant = serde_json::from_value(serde_json::json!({
+        "_id": "tenant-1",
+        "identifier": "brand-a",
+    }))
+    .unwrap();
+
+    let trigger_tenant = TriggerTenant::from(&tenant);
+    assert_eq!(serde_json::to_value(&trigger_tenant).unwrap(), "brand-a");
+}
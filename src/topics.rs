@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Client,
+    error::NovuError,
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::{encode_path_segment, generate_query_string},
+};
+
+/// A named group of subscribers that a workflow can be triggered for.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Topic {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_environmentId")]
+    pub environment_id: Option<String>,
+    #[serde(rename = "_organizationId")]
+    pub organization_id: Option<String>,
+    pub key: String,
+    pub name: Option<String>,
+    /// The ids of the subscribers in the topic.
+    #[serde(default)]
+    pub subscribers: Vec<String>,
+}
+
+pub type TopicsResponse = Page<Topic>;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTopicPayload {
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTopicResponse {
+    #[serde(rename = "_id")]
+    pub id: Option<String>,
+    pub key: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RenameTopicPayload<'a> {
+    name: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TopicSubscribersPayload<'a> {
+    subscribers: &'a [String],
+}
+
+/// Which subscribers were added to or removed from a topic.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicSubscribersResponse {
+    #[serde(default)]
+    pub succeeded: Vec<String>,
+    #[serde(default)]
+    pub failed: TopicSubscribersFailures,
+}
+
+impl TopicSubscribersResponse {
+    /// Whether no id failed, for whatever reason.
+    pub fn is_success(&self) -> bool {
+        self.failed.not_found.is_empty() && self.failed.other.values().all(Vec::is_empty)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicSubscribersFailures {
+    /// Ids that don't belong to any subscriber.
+    #[serde(default)]
+    pub not_found: Vec<String>,
+    /// Failures for reasons this version of the crate doesn't know, keyed by
+    /// Novu's name for the reason.
+    #[serde(flatten)]
+    pub other: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicSubscriber {
+    #[serde(rename = "_organizationId")]
+    pub organization_id: String,
+    #[serde(rename = "_environmentId")]
+    pub environment_id: String,
+    #[serde(rename = "_subscriberId")]
+    pub subscriber_id: String,
+    #[serde(rename = "_topicId")]
+    pub topic_id: String,
+    pub topic_key: String,
+    pub external_subscriber_id: String,
+}
+
+#[derive(Clone)]
+pub struct Topics {
+    client: Client,
+}
+
+impl Topics {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
+    pub async fn create(
+        &self,
+        key: impl ToString,
+        name: impl ToString,
+    ) -> Result<CreateTopicResponse, NovuError> {
+        let payload = CreateTopicPayload {
+            key: key.to_string(),
+            name: name.to_string(),
+        };
+
+        self.client.post("/topics", Some(&payload)).await
+    }
+
+    /// Lists topics, optionally only the one with the given `key`.
+    pub async fn list(
+        &self,
+        page: Option<u32>,
+        page_size: Option<u32>,
+        key: Option<&str>,
+    ) -> Result<TopicsResponse, NovuError> {
+        let mut params = HashMap::new();
+        params.insert("page", page.map(|p| p.to_string()));
+        params.insert("pageSize", page_size.map(|p| p.to_string()));
+        params.insert("key", key.map(|k| k.to_string()));
+
+        self.client
+            .get_paginated(format!("/topics?{}", generate_query_string(&params)))
+            .await
+    }
+
+    /// Streams every topic, fetching pages as the stream is polled.
+    pub fn list_all(&self, options: PageOptions) -> impl Stream<Item = Result<Topic, NovuError>> {
+        let topics = self.clone();
+
+        paginate(options, move |page| {
+            let topics = topics.clone();
+            async move { topics.list(Some(page), options.page_size, None).await }
+        })
+    }
+
+    pub async fn get(&self, key: impl AsRef<str>) -> Result<Topic, NovuError> {
+        self.client
+            .get(format!("/topics/{}", encode_path_segment(key.as_ref())))
+            .await
+    }
+
+    pub async fn rename(
+        &self,
+        key: impl AsRef<str>,
+        name: impl AsRef<str>,
+    ) -> Result<Topic, NovuError> {
+        let payload = RenameTopicPayload {
+            name: name.as_ref(),
+        };
+
+        self.client
+            .patch(
+                format!("/topics/{}", encode_path_segment(key.as_ref())),
+                Some(&payload),
+            )
+            .await
+    }
+
+    pub async fn delete(&self, key: impl AsRef<str>) -> Result<(), NovuError> {
+        self.client
+            .delete(format!("/topics/{}", encode_path_segment(key.as_ref())))
+            .await
+    }
+
+    pub async fn add_subscribers(
+        &self,
+        key: impl AsRef<str>,
+        subscriber_ids: &[String],
+    ) -> Result<TopicSubscribersResponse, NovuError> {
+        let payload = TopicSubscribersPayload {
+            subscribers: subscriber_ids,
+        };
+
+        self.client
+            .post(
+                format!("/topics/{}/subscribers", encode_path_segment(key.as_ref())),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// Novu usually answers removals without a body, in which case this
+    /// returns `None`: the request went through, but Novu didn't say which
+    /// ids were removed.
+    pub async fn remove_subscribers(
+        &self,
+        key: impl AsRef<str>,
+        subscriber_ids: &[String],
+    ) -> Result<Option<TopicSubscribersResponse>, NovuError> {
+        let payload = TopicSubscribersPayload {
+            subscribers: subscriber_ids,
+        };
+
+        self.client
+            .post(
+                format!(
+                    "/topics/{}/subscribers/removal",
+                    encode_path_segment(key.as_ref())
+                ),
+                Some(&payload),
+            )
+            .await
+    }
+
+    /// Whether the subscriber is in the topic.
+    pub async fn has_subscriber(
+        &self,
+        key: impl AsRef<str>,
+        subscriber_id: impl AsRef<str>,
+    ) -> Result<bool, NovuError> {
+        let result: Result<TopicSubscriber, NovuError> = self
+            .client
+            .get(format!(
+                "/topics/{}/subscribers/{}",
+                encode_path_segment(key.as_ref()),
+                encode_path_segment(subscriber_id.as_ref())
+            ))
+            .await;
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(NovuError::NotFound { .. }) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_topic_lifecycle() {
+    use wiremock::{
+        matchers::{body_json, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/topics"))
+        .and(body_json(
+            serde_json::json!({ "key": "account-42", "name": "Account 42" }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(
+            serde_json::json!({ "data": { "_id": "topic-1", "key": "account-42" } }),
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/topics"))
+        .and(query_param("key", "account-42"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 10,
+            "totalCount": 1,
+            "data": [{ "_id": "topic-1", "key": "account-42", "name": "Account 42", "subscribers": ["ada"] }],
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PATCH"))
+        .and(path("/v1/topics/account-42"))
+        .and(body_json(serde_json::json!({ "name": "Holders" })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "_id": "topic-1", "key": "account-42", "name": "Holders" },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/v1/topics/account-42"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let created = novu
+        .topics
+        .create("account-42", "Account 42")
+        .await
+        .unwrap();
+    assert_eq!(created.key, "account-42");
+
+    let page = novu
+        .topics
+        .list(None, None, Some("account-42"))
+        .await
+        .unwrap();
+    assert_eq!(page.data[0].subscribers, ["ada"]);
+
+    let renamed = novu.topics.rename("account-42", "Holders").await.unwrap();
+    assert_eq!(renamed.name.as_deref(), Some("Holders"));
+    assert!(renamed.subscribers.is_empty());
+
+    novu.topics.delete("account-42").await.unwrap();
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_topic_subscribers() {
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/topics/admins/subscribers"))
+        .and(body_json(
+            serde_json::json!({ "subscribers": ["ada", "bob"] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "succeeded": ["ada"], "failed": { "notFound": ["bob"] } },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/topics/team%2Fadmins/subscribers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": { "succeeded": [], "failed": { "notFound": [], "blocked": ["eve"] } },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/topics/admins/subscribers/removal"))
+        .and(body_json(serde_json::json!({ "subscribers": ["ada"] })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/topics/admins/subscribers/ada"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "data": {
+                "_organizationId": "org-1",
+                "_environmentId": "env-1",
+                "_subscriberId": "s-1",
+                "_topicId": "topic-1",
+                "topicKey": "admins",
+                "externalSubscriberId": "ada",
+            },
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/topics/admins/subscribers/bob"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(
+            serde_json::json!({ "statusCode": 404, "message": "Subscriber not found" }),
+        ))
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let added = novu
+        .topics
+        .add_subscribers("admins", &["ada".to_string(), "bob".to_string()])
+        .await
+        .unwrap();
+    assert!(!added.is_success());
+    assert_eq!(added.succeeded, ["ada"]);
+    assert_eq!(added.failed.not_found, ["bob"]);
+
+    let added = novu
+        .topics
+        .add_subscribers("team/admins", &["eve".to_string()])
+        .await
+        .unwrap();
+    assert!(!added.is_success());
+    assert_eq!(added.failed.other["blocked"], ["eve"]);
+
+    let removed = novu
+        .topics
+        .remove_subscribers("admins", &["ada".to_string()])
+        .await
+        .unwrap();
+    assert!(removed.is_none());
+
+    assert!(novu.topics.has_subscriber("admins", "ada").await.unwrap());
+    assert!(!novu.topics.has_subscriber("admins", "bob").await.unwrap());
+}
@@ -1,17 +1,145 @@
 use std::collections::HashMap;
 
-// A utility function to generate the query string for multiple parameters
+// A utility function to generate the query string for multiple parameters,
+// percent-encoding the values
 pub fn generate_query_string(params: &HashMap<&str, Option<impl ToString>>) -> String {
-    let mut query_string = String::new();
+    let mut query = form_urlencoded::Serializer::new(String::new());
 
     for (key, value_option) in params {
         if let Some(value) = value_option {
-            if !query_string.is_empty() {
-                query_string.push('&');
+            query.append_pair(key, &value.to_string());
+        }
+    }
+
+    query.finish()
+}
+
+/// Percent-encodes `segment` so it stays one segment of a URL path, e.g. a
+/// topic key containing a `/` or a space.
+pub(crate) fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
             }
-            query_string.push_str(&format!("{}={}", key, value.to_string()));
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
         }
     }
 
-    query_string
+    encoded
+}
+
+/// Declares a unit enum that goes over the wire as one of Novu's string
+/// values, with an `Unknown` variant keeping values this crate doesn't know
+/// about yet.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident => $value:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant,)*
+            /// A value added to Novu after this version of the crate.
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Every known variant.
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)*
+                    $name::Unknown(value) => value,
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => $name::$variant,)*
+                    value => $name::Unknown(value.to_string()),
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ::std::convert::Infallible;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok($name::from(value))
+            }
+        }
+
+        impl ::serde::Serialize for $name {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok($name::from(value.as_str()))
+            }
+        }
+    };
+}
+
+pub(crate) use string_enum;
+
+/// Checks that every variant of a [`string_enum`] survives a JSON round
+/// trip and that unknown values are kept.
+#[cfg(test)]
+pub(crate) fn assert_string_enum<T>(all: &[T], expected: &[&str])
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug + PartialEq + ToString,
+{
+    assert_eq!(all.len(), expected.len());
+
+    for (variant, value) in all.iter().zip(expected) {
+        assert_eq!(serde_json::to_value(variant).unwrap(), *value);
+        assert_eq!(variant.to_string(), *value);
+        assert_eq!(
+            &serde_json::from_value::<T>(serde_json::json!(value)).unwrap(),
+            variant
+        );
+    }
+
+    let unknown: T = serde_json::from_value(serde_json::json!("added-later")).unwrap();
+    assert_eq!(serde_json::to_value(&unknown).unwrap(), "added-later");
+}
+
+#[cfg(test)]
+#[test]
+fn test_encode_path_segment() {
+    assert_eq!(encode_path_segment("account-42_a.b~"), "account-42_a.b~");
+    assert_eq!(
+        encode_path_segment("team/admins?all #1"),
+        "team%2Fadmins%3Fall%20%231"
+    );
+    assert_eq!(encode_path_segment("café"), "caf%C3%A9");
+}
+
+#[cfg(test)]
+#[test]
+fn test_generate_query_string_encodes_values() {
+    let mut params = HashMap::new();
+    params.insert("key", Some("a&b=c+d#e"));
+    params.insert("page", None);
+
+    assert_eq!(generate_query_string(&params), "key=a%26b%3Dc%2Bd%23e");
 }
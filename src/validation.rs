@@ -0,0 +1,342 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts::TRIGGER_VALIDATOR_REFRESH_INTERVAL,
+    error::NovuError,
+    events::TriggerPayload,
+    pagination::PageOptions,
+    workflows::{Trigger, Workflows},
+};
+
+/// Checks trigger payloads against the variables of their workflow before
+/// they are sent.
+///
+/// Trigger definitions are fetched with the list of workflows and cached
+/// until [`TriggerValidator::clear`] is called. An identifier missing from
+/// the cache fetches the list again, at most once per refresh interval, so
+/// unknown workflows don't cost a walk over every workflow each time.
+#[derive(Clone)]
+pub struct TriggerValidator {
+    workflows: Workflows,
+    cache: Arc<Mutex<TriggerCache>>,
+    refresh_interval: Duration,
+    lenient: bool,
+}
+
+/// The part of a workflow the validator reads. Decoding only this keeps
+/// the rest of the workflow, e.g. its steps, from failing a refresh.
+#[derive(Deserialize)]
+struct WorkflowTriggers {
+    #[serde(default)]
+    triggers: Vec<Trigger>,
+}
+
+#[derive(Default)]
+struct TriggerCache {
+    triggers: HashMap<String, Trigger>,
+    refreshed_at: Option<Instant>,
+}
+
+impl TriggerValidator {
+    pub fn new(workflows: Workflows) -> Self {
+        Self {
+            workflows,
+            cache: Arc::default(),
+            refresh_interval: TRIGGER_VALIDATOR_REFRESH_INTERVAL,
+            lenient: false,
+        }
+    }
+
+    /// How long an unknown identifier is reported as such before the list of
+    /// workflows is fetched again.
+    pub fn refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Only requires variables marked as required, so a missing variable
+    /// without a default value renders as a blank instead of failing. See
+    /// [`Trigger::check_payload_lenient`].
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Rejects payloads that miss required variables or give them the wrong
+    /// type with [`NovuError::InvalidPayload`], and unknown workflows with
+    /// [`NovuError::TemplateNotFound`].
+    pub async fn validate<T: Serialize>(&self, data: &TriggerPayload<T>) -> Result<(), NovuError> {
+        self.validate_payload(&data.name, &data.payload).await
+    }
+
+    /// Like [`TriggerValidator::validate`], for a bare workflow identifier and
+    /// payload, e.g. those of a broadcast.
+    pub async fn validate_payload(
+        &self,
+        identifier: &str,
+        payload: &impl Serialize,
+    ) -> Result<(), NovuError> {
+        let trigger = self.trigger(identifier).await?;
+        let payload = serde_json::to_value(payload).map_err(|err| {
+            NovuError::InvalidValues("serializing payload".to_string(), err.to_string())
+        })?;
+
+        match self.lenient {
+            true => trigger.check_payload_lenient(&payload),
+            false => trigger.check_payload(&payload),
+        }
+    }
+
+    /// The trigger definition of the workflow with this identifier.
+    pub async fn trigger(&self, identifier: &str) -> Result<Trigger, NovuError> {
+        {
+            let cache = self.cache.lock().unwrap();
+            if let Some(trigger) = cache.triggers.get(identifier) {
+                return Ok(trigger.clone());
+            }
+
+            let fresh = cache
+                .refreshed_at
+                .map_or(false, |at| at.elapsed() < self.refresh_interval);
+            if fresh {
+                return Err(NovuError::TemplateNotFound(identifier.to_string()));
+            }
+        }
+
+        self.refresh().await?;
+
+        self.cache
+            .lock()
+            .unwrap()
+            .triggers
+            .get(identifier)
+            .cloned()
+            .ok_or_else(|| NovuError::TemplateNotFound(identifier.to_string()))
+    }
+
+    /// Workflows can only be fetched by id, so walk the whole list and cache
+    /// every trigger on it.
+    async fn refresh(&self) -> Result<(), NovuError> {
+        let mut triggers = HashMap::new();
+        let mut workflows = Box::pin(
+            self.workflows
+                .list_all_as::<WorkflowTriggers>(PageOptions::new().page_size(100)),
+        );
+        while let Some(workflow) = workflows.next().await {
+            for trigger in workflow?.triggers {
+                triggers.insert(trigger.identifier.clone(), trigger);
+            }
+        }
+
+        let mut cache = self.cache.lock().unwrap();
+        cache.triggers = triggers;
+        cache.refreshed_at = Some(Instant::now());
+
+        Ok(())
+    }
+
+    pub fn clear(&self) {
+        *self.cache.lock().unwrap() = TriggerCache::default();
+    }
+}
+
+#[cfg(test)]
+fn test_workflow(identifier: &str, variables: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "name": identifier,
+        "description": "",
+        "active": true,
+        "preferenceSettings": { "email": true, "sms": true, "in_app": true, "chat": true, "push": true },
+        "critical": false,
+        "tags": [],
+        "steps": [{
+            "_id": "step-1",
+            "_templateId": "template-1",
+            "active": true,
+            "filters": [],
+            "template": { "type": "email", "subject": "Invoice paid" },
+        }],
+        "triggers": [{
+            "type": "event",
+            "identifier": identifier,
+            "variables": variables,
+            "subscriberVariables": [],
+        }],
+    })
+}
+
+#[cfg(test)]
+#[test]
+fn test_check_payload() {
+    let trigger: Trigger = serde_json::from_value(
+        test_workflow(
+            "invoice-paid",
+            serde_json::json!([
+                { "name": "amount", "type": "String" },
+                { "name": "customer.name", "type": "String" },
+                { "name": "items", "type": "Array" },
+                { "name": "footer", "type": "String", "defaultValue": "Thanks!" },
+            ]),
+        )["triggers"][0]
+            .clone(),
+    )
+    .unwrap();
+
+    assert!(trigger
+        .check_payload(&serde_json::json!({
+            "amount": 42,
+            "customer": { "name": "Ada" },
+            "items": [],
+        }))
+        .is_ok());
+    // None of the variables is marked as required.
+    assert!(trigger
+        .check_payload_lenient(&serde_json::json!({}))
+        .is_ok());
+    match trigger.check_payload(&serde_json::json!({ "amuont": 42 })) {
+        Err(NovuError::InvalidPayload { missing, .. }) => {
+            assert_eq!(missing, ["amount", "customer.name", "items"])
+        }
+        other => panic!("expected an invalid payload, got {:?}", other),
+    }
+
+    match trigger.check_payload(&serde_json::json!({
+        "amount": "42",
+        "customer": { "nmae": "Ada" },
+        "items": "none",
+        "footer": null,
+    })) {
+        Err(NovuError::InvalidPayload {
+            workflow,
+            missing,
+            mistyped,
+        }) => {
+            assert_eq!(workflow, "invoice-paid");
+            assert_eq!(missing, ["customer.name"]);
+            assert_eq!(mistyped, ["items"]);
+        }
+        other => panic!("expected an invalid payload, got {:?}", other),
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_trigger_validation_is_cached() {
+    use crate::{events::TriggerRecipientsType, Novu};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/workflows/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 100,
+            "totalCount": 2,
+            "data": [
+                test_workflow("welcome", serde_json::json!([])),
+                test_workflow(
+                    "invoice-paid",
+                    serde_json::json!([{ "name": "amount", "required": true }]),
+                ),
+            ],
+        })))
+        // "unknown" is missing from the list fetched for "invoice-paid", and
+        // isn't fetched again within the refresh interval.
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/events/trigger"))
+        .respond_with(ResponseTemplate::new(201).set_body_string(
+            r#"{"data":{"acknowledged":true,"status":"processed","transactionId":"txn"}}"#,
+        ))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let novu = Novu::new("", Some(&server.uri()))
+        .unwrap()
+        .with_trigger_validation();
+    let trigger = |payload: serde_json::Value| {
+        novu.trigger(
+            TriggerPayload::builder(
+                "invoice-paid",
+                TriggerRecipientsType::builder()
+                    .subscriber_id("sub-1")
+                    .build()
+                    .unwrap(),
+            )
+            .payload(payload)
+            .build(),
+        )
+    };
+
+    let result = trigger(serde_json::json!({ "amuont": 42 })).await;
+    assert!(
+        matches!(&result, Err(NovuError::InvalidPayload { missing, .. }) if missing == &["amount"]),
+        "{:?}",
+        result
+    );
+    assert!(trigger(serde_json::json!({ "amount": 42 })).await.is_ok());
+
+    let unknown = novu
+        .trigger(
+            TriggerPayload::builder(
+                "unknown",
+                TriggerRecipientsType::builder()
+                    .subscriber_id("sub-1")
+                    .build()
+                    .unwrap(),
+            )
+            .build(),
+        )
+        .await;
+    assert!(
+        matches!(unknown, Err(NovuError::TemplateNotFound(_))),
+        "{:?}",
+        unknown
+    );
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_unknown_workflow_refreshes_after_interval() {
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/workflows/"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "page": 0,
+            "pageSize": 100,
+            "totalCount": 1,
+            "data": [test_workflow("welcome", serde_json::json!([]))],
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let novu = crate::Novu::new("", Some(&server.uri())).unwrap();
+    let validator = TriggerValidator::new(novu.workflows.clone());
+    for _ in 0..3 {
+        let result = validator.trigger("unknown").await;
+        assert!(matches!(result, Err(NovuError::TemplateNotFound(_))));
+    }
+
+    let validator = validator.refresh_interval(Duration::ZERO);
+    assert!(validator.trigger("unknown").await.is_err());
+}
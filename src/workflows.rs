@@ -1,11 +1,16 @@
 use std::collections::HashMap;
 
 use crate::{
-    client::{Client, Response},
+    client::Client,
     error::NovuError,
-    utils::generate_query_string,
+    events::{TriggerPayload, TriggerPayloadBuilder, TriggerRecipientsType},
+    options::RequestOptions,
+    pagination::{paginate, Page, PageOptions},
+    utils::{generate_query_string, string_enum},
+    ChannelTypeEnum,
 };
-use serde::{Deserialize, Serialize};
+use futures::Stream;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 
 #[derive(Clone)]
@@ -13,16 +18,45 @@ pub struct Workflows {
     client: Client,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+/// Whether each channel is enabled. Channels Novu leaves out are enabled,
+/// matching Novu's own default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PreferenceSettings {
     pub email: bool,
     pub sms: bool,
+    #[serde(alias = "inApp")]
     pub in_app: bool,
     pub chat: bool,
     pub push: bool,
 }
 
+impl PreferenceSettings {
+    /// Whether `channel` is enabled; unknown channels never are.
+    pub fn is_enabled(&self, channel: &ChannelTypeEnum) -> bool {
+        match channel {
+            ChannelTypeEnum::InApp => self.in_app,
+            ChannelTypeEnum::EMAIL => self.email,
+            ChannelTypeEnum::SMS => self.sms,
+            ChannelTypeEnum::CHAT => self.chat,
+            ChannelTypeEnum::PUSH => self.push,
+            ChannelTypeEnum::Unknown(_) => false,
+        }
+    }
+}
+
+impl Default for PreferenceSettings {
+    fn default() -> Self {
+        Self {
+            email: true,
+            sms: true,
+            in_app: true,
+            chat: true,
+            push: true,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Child {
@@ -59,42 +93,197 @@ pub struct MetaData {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Step {
+    #[serde(rename = "_id")]
     pub _id: String,
     pub uuid: String,
     pub name: String,
+    #[serde(rename = "_templateId")]
     pub _template_id: String,
     pub active: bool,
     pub should_stop_on_fail: bool,
     pub template: Option<Value>,
     pub filters: Vec<Filter>,
+    #[serde(rename = "_parentId")]
     pub _parent_id: Option<Value>,
-    pub metadata: MetaData,
+    pub metadata: Option<MetaData>,
     pub reply_callback: Option<Value>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Trigger {
     #[serde(rename = "type")]
     pub trigger_type: String,
     pub identifier: String,
-    pub variables: Vec<Value>,
-    pub subscriber_variables: Vec<Value>,
+    pub variables: Vec<TriggerVariable>,
+    pub subscriber_variables: Vec<TriggerVariable>,
+}
+
+/// A variable used by a workflow's templates, e.g. `user.name` for
+/// `{{user.name}}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerVariable {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub variable_type: Option<TriggerVariableType>,
+    pub default_value: Option<Value>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl TriggerVariable {
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Whether the variable is required, or would render as a blank when
+    /// missing because it has no default value.
+    pub fn needs_value(&self) -> bool {
+        self.required || self.default_value.is_none()
+    }
+}
+
+string_enum! {
+    pub enum TriggerVariableType {
+        String => "String",
+        Array => "Array",
+        Boolean => "Boolean",
+    }
+}
+
+impl TriggerVariableType {
+    /// Whether `value` can fill a variable of this type. Numbers are
+    /// accepted for strings since templates render them the same way.
+    pub fn accepts(&self, value: &Value) -> bool {
+        match self {
+            TriggerVariableType::String => value.is_string() || value.is_number(),
+            TriggerVariableType::Array => value.is_array(),
+            TriggerVariableType::Boolean => value.is_boolean(),
+            TriggerVariableType::Unknown(_) => true,
+        }
+    }
+}
+
+impl Trigger {
+    /// Checks a serialized trigger payload against the workflow's variables
+    /// and lists the keys that are missing or have the wrong type. Variables
+    /// without a default value count as missing, see
+    /// [`TriggerVariable::needs_value`].
+    pub fn check_payload(&self, payload: &Value) -> Result<(), NovuError> {
+        self.check(payload, TriggerVariable::needs_value)
+    }
+
+    /// Like [`Trigger::check_payload`], but only requires variables marked
+    /// as required, so other keys are only checked for their type.
+    pub fn check_payload_lenient(&self, payload: &Value) -> Result<(), NovuError> {
+        self.check(payload, TriggerVariable::is_required)
+    }
+
+    fn check(
+        &self,
+        payload: &Value,
+        is_required: fn(&TriggerVariable) -> bool,
+    ) -> Result<(), NovuError> {
+        let mut missing = Vec::new();
+        let mut mistyped = Vec::new();
+
+        for variable in &self.variables {
+            let value = variable
+                .name
+                .split('.')
+                .try_fold(payload, |value, key| value.get(key))
+                .filter(|value| !value.is_null());
+
+            match (value, &variable.variable_type) {
+                (None, _) if is_required(variable) => missing.push(variable.name.clone()),
+                (Some(value), Some(variable_type)) if !variable_type.accepts(value) => {
+                    mistyped.push(variable.name.clone())
+                }
+                _ => {}
+            }
+        }
+
+        if missing.is_empty() && mistyped.is_empty() {
+            return Ok(());
+        }
+
+        Err(NovuError::InvalidPayload {
+            workflow: self.identifier.clone(),
+            missing,
+            mistyped,
+        })
+    }
+
+    /// The top-level payload keys the workflow's templates use, e.g.
+    /// `user` for `{{user.name}}`.
+    pub fn variable_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .variables
+            .iter()
+            .map(|variable| {
+                let name = variable.name.as_str();
+                name.split('.').next().unwrap_or(name).to_string()
+            })
+            .collect();
+
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// A payload type bound to one workflow, usually implemented with
+/// `#[derive(NovuWorkflow)]` from the `derive` feature.
+pub trait NovuWorkflow: Serialize + Sized {
+    const WORKFLOW_ID: &'static str;
+    /// The payload keys `Self` serializes to.
+    const VARIABLES: &'static [&'static str];
+
+    fn trigger_payload(self, to: TriggerRecipientsType) -> TriggerPayloadBuilder<Self> {
+        TriggerPayload::builder(Self::WORKFLOW_ID, to).payload(self)
+    }
+
+    /// Variables the workflow's templates use that `Self` doesn't provide.
+    fn missing_variables(trigger: &Trigger) -> Vec<String> {
+        trigger
+            .variable_names()
+            .into_iter()
+            .filter(|name| !Self::VARIABLES.contains(&name.as_str()))
+            .collect()
+    }
+
+    /// Fields of `Self` that none of the workflow's templates use, which
+    /// usually means a misspelled key.
+    fn unused_variables(trigger: &Trigger) -> Vec<String> {
+        let names = trigger.variable_names();
+
+        Self::VARIABLES
+            .iter()
+            .filter(|variable| !names.iter().any(|name| name == *variable))
+            .map(|variable| variable.to_string())
+            .collect()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationGroup {
+    #[serde(rename = "_id")]
     pub _id: String,
     pub name: String,
+    #[serde(rename = "_environmentId")]
     pub _environment_id: String,
+    #[serde(rename = "_organizationId")]
     pub _organization_id: String,
+    #[serde(rename = "_parentId")]
     pub _parent_id: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Workflow {
+    #[serde(rename = "_id")]
     pub _id: Option<String>,
     pub name: String,
     pub description: String,
@@ -104,12 +293,17 @@ pub struct Workflow {
     pub critical: bool,
     pub tags: Vec<String>,
     pub steps: Vec<Step>,
+    #[serde(rename = "_organizationId")]
     pub _organization_id: Option<String>,
+    #[serde(rename = "_creatorId")]
     pub _creator_id: Option<String>,
+    #[serde(rename = "_environmentId")]
     pub _environment_id: Option<String>,
     pub triggers: Vec<Trigger>,
+    #[serde(rename = "_notificationGroupId")]
     pub _notification_group_id: Option<String>,
     pub notification_group_id: Option<String>,
+    #[serde(rename = "_parentId")]
     pub _parent_id: Option<String>,
     pub deleted: Option<bool>,
     pub deleted_at: Option<String>,
@@ -120,11 +314,7 @@ pub struct Workflow {
     pub blueprint_id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct WorkflowsResponse {
-    pub data: Vec<Workflow>,
-}
+pub type WorkflowsResponse = Page<Workflow>;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -143,99 +333,126 @@ impl Workflows {
         Self { client }
     }
 
+    /// Returns a copy that sends `options` with its requests.
+    pub fn with_options(&self, options: RequestOptions) -> Self {
+        Self::new(self.client.clone_client().with_options(options))
+    }
+
     pub async fn list(
         &self,
         page: Option<i32>,
         limit: Option<i32>,
     ) -> Result<WorkflowsResponse, NovuError> {
+        self.list_as(page, limit).await
+    }
+
+    /// Like [`Workflows::list`], decoding each workflow as `W`, e.g. a
+    /// struct with only the fields the caller reads.
+    pub(crate) async fn list_as<W: DeserializeOwned>(
+        &self,
+        page: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Page<W>, NovuError> {
         let mut params: HashMap<&str, Option<String>> = HashMap::new();
         params.insert("page", page.map(|p| p.to_string()));
         params.insert("limit", limit.map(|l| l.to_string()));
 
-        let result = self
-            .client
-            .get(format!("/workflows/?{}", generate_query_string(&params)))
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/workflows".to_string())),
-                400 => Err(NovuError::InvalidValues(
-                    "page size".to_string(),
-                    "page size limit".to_string(),
-                )),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client
+            .get_paginated(format!("/workflows/?{}", generate_query_string(&params)))
+            .await
+    }
+
+    /// Streams every workflow, fetching pages as the stream is polled.
+    pub fn list_all(
+        &self,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<Workflow, NovuError>> {
+        self.list_all_as(options)
+    }
+
+    pub(crate) fn list_all_as<W: DeserializeOwned>(
+        &self,
+        options: PageOptions,
+    ) -> impl Stream<Item = Result<W, NovuError>> {
+        let workflows = self.clone();
+
+        paginate(options, move |page| {
+            let workflows = workflows.clone();
+            async move {
+                workflows
+                    .list_as(Some(page as i32), options.page_size.map(|size| size as i32))
+                    .await
+            }
+        })
     }
 
     pub async fn create(&self, data: Workflow) -> Result<Workflow, NovuError> {
-        let result = self.client.post("/workflows", Some(&data)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/workflows".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.post("/workflows", Some(&data)).await
     }
 
     pub async fn update(&self, id: String, data: Workflow) -> Result<Workflow, NovuError> {
-        let result = self.client.put(format!("/workflows/{}", id), &data).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/workflows".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.put(format!("/workflows/{}", id), &data).await
     }
 
     pub async fn delete(&self, id: String) -> Result<WorkflowDeleteResponse, NovuError> {
-        let result = self.client.delete(format!("/workflows/{}", id)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/workflows".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.delete(format!("/workflows/{}", id)).await
     }
 
     pub async fn get(&self, id: String) -> Result<Workflow, NovuError> {
-        let result = self.client.get(format!("/workflows/{}", id)).await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/workflows".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+        self.client.get(format!("/workflows/{}", id)).await
     }
 
     pub async fn update_status(&self, id: String) -> Result<WorkflowStatusResponse, NovuError> {
-        let result = self
-            .client
+        self.client
             .put(format!("/workflows/{}/status", id), &None::<()>)
-            .await?;
-
-        match result {
-            Response::Success(data) => Ok(data.data),
-            Response::Error(err) => match err.status_code {
-                401 => Err(NovuError::UnauthorizedError("/workflows".to_string())),
-                code => todo!("{}", code),
-            },
-            Response::Messages(err) => todo!("{:?}", err),
-        }
+            .await
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_trigger_variable_type_round_trip() {
+    crate::utils::assert_string_enum(TriggerVariableType::ALL, &["String", "Array", "Boolean"]);
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_workflow_with_steps() {
+    let workflow: Workflow = serde_json::from_value(serde_json::json!({
+        "_id": "workflow-1",
+        "name": "Invoice paid",
+        "description": "",
+        "active": true,
+        "draft": false,
+        "preferenceSettings": { "email": true, "sms": true, "in_app": true, "chat": true, "push": true },
+        "critical": false,
+        "tags": [],
+        "steps": [{
+            "_id": "step-1",
+            "uuid": "6c2b3c4e",
+            "name": "Email",
+            "_templateId": "template-1",
+            "active": true,
+            "shouldStopOnFail": false,
+            "filters": [],
+            "_parentId": null,
+        }],
+        "_organizationId": "org-1",
+        "_creatorId": "user-1",
+        "_environmentId": "env-1",
+        "triggers": [{
+            "type": "event",
+            "identifier": "invoice-paid",
+            "variables": [{ "name": "amount" }],
+            "subscriberVariables": [],
+        }],
+        "_notificationGroupId": "group-1",
+    }))
+    .unwrap();
+
+    assert_eq!(workflow._id.as_deref(), Some("workflow-1"));
+    assert_eq!(workflow.steps[0]._id, "step-1");
+    assert_eq!(workflow.steps[0]._template_id, "template-1");
+    assert!(workflow.steps[0].metadata.is_none());
+    assert_eq!(workflow._notification_group_id.as_deref(), Some("group-1"));
+}